@@ -14,10 +14,77 @@
 
 #[ink::contract]
 mod paragoal_betting {
-    use ink::prelude::vec;
-    use ink::storage::Mapping;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::{Lazy, Mapping};
     use ink::storage::traits::StorageLayout;
 
+    // 常量: 存储布局版本与升级时间锁 / Constants: Storage layout version and upgrade timelock
+    // 中文: STORAGE_VERSION 为当前代码使用的存储布局版本；升级提案需等待 UPGRADE_TIMELOCK_MS（毫秒）后才能执行。
+    // English: STORAGE_VERSION is the storage layout this code expects; an upgrade proposal can only be executed after
+    //  UPGRADE_TIMELOCK_MS milliseconds.
+    pub const STORAGE_VERSION: u32 = 4;
+    pub const UPGRADE_TIMELOCK_MS: u64 = 2 * 24 * 60 * 60 * 1000;
+
+    // 常量: 单批次派发上限 / Constant: Max users per distribute batch
+    // 中文: 限制 distribute 单次处理的用户数，避免超出区块权重。
+    // English: Caps the users handled by one distribute call so it fits block weight limits.
+    pub const MAX_DISTRIBUTE_BATCH: usize = 50;
+
+    // 常量: 单次批量领取的比赛数上限 / Constant: Max matches per claim_many call
+    pub const MAX_CLAIM_BATCH: usize = 50;
+
+    // 常量: 分页查询单页上限 / Constant: Max page size for paginated queries
+    pub const MAX_PAGE_SIZE: u32 = 100;
+
+    // 常量: 队伍字段长度上限（字节） / Constant: Max length of team text fields (bytes)
+    pub const MAX_TEAM_FIELD_LEN: usize = 64;
+
+    // 常量: 比赛元数据文本字段长度上限（字节） / Constant: Max length of match metadata text fields (bytes)
+    pub const MAX_METADATA_FIELD_LEN: usize = 128;
+
+    // 常量: 旧版本中无法识别的队伍标识 / Constant: Team id for unrecognised legacy identifiers
+    pub const UNKNOWN_TEAM: TeamId = TeamId::MAX;
+
+    // 常量: 内置队伍（名称、简称、国家代码） / Constant: Built-in teams (name, short code, country code)
+    // 中文: 按顺序注册，ID 0..7 与旧版本的占位符 [n; 32] 一一对应。
+    // English: Registered in order, so ids 0..7 line up with the legacy [n; 32] placeholders.
+    const BUILT_IN_TEAMS: [(&str, &str, &str); 8] = [
+        ("Croatia", "CRO", "HR"),
+        ("Brazil", "BRA", "BR"),
+        ("Netherlands", "NED", "NL"),
+        ("Argentina", "ARG", "AR"),
+        ("Morocco", "MAR", "MA"),
+        ("Portugal", "POR", "PT"),
+        ("England", "ENG", "GB"),
+        ("France", "FRA", "FR"),
+    ];
+
+    // 常量: 淘汰赛首轮比赛数上限 / Constant: Max first-round matches in a knockout bracket
+    pub const MAX_BRACKET_FIRST_ROUND: u32 = 32;
+
+    // 平局晋级处理期限（7天，毫秒），逾期未指定晋级者时冠军盘可手动结算
+    // Window to resolve a drawn bracket match (7 days, ms); once it lapses the outright market may be settled by hand
+    pub const DRAW_RESOLUTION_WINDOW_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+
+    // 类型别名: 队伍ID / Type alias: Team id
+    pub type TeamId = u32;
+
+    // 类型别名: 赛事ID / Type alias: Tournament id
+    pub type TournamentId = u32;
+
+    // 常量: 赔率精度 / Constant: Odds precision
+    // 中文: 隐含赔率以 ODDS_PRECISION 为 1.0 的定点数存储，例如 25_000 表示 2.5 倍。
+    // English: Implied odds are fixed-point with ODDS_PRECISION as 1.0, e.g. 25_000 means 2.5x.
+    pub const ODDS_PRECISION: u128 = 10_000;
+
+    // 常量: 基点分母与默认提前退出罚金 / Constants: Basis-point denominator and default early-exit penalty
+    pub const BPS_DENOMINATOR: u32 = 10_000;
+    pub const DEFAULT_EARLY_EXIT_PENALTY_BPS: u32 = 500;
+
+    // 常量: 平台手续费（基点，有首次注入者时收取） / Constant: Platform fee in basis points (charged when a first injector exists)
+    pub const FEE_BPS: u32 = 500;
+
     // 枚举定义: 比赛状态 / Enum: Match Status
     // 中文: 定义比赛的生命周期状态，从Pending开始，到Settled结束。初学者: 枚举是Rust中定义固定选项的方式，这里用于状态机控制。
     // English: Defines the lifecycle states of a match, from Pending to Settled. For beginners: Enums in Rust define fixed options, used here for state machine control.
@@ -52,6 +119,33 @@ mod paragoal_betting {
         Draw,    // 平局 / Draw
     }
 
+    // 枚举定义: 角色 / Enum: Role
+    // 中文: 合约级权限角色。Owner 可授予/撤销角色，并隐含拥有其他所有角色的权限。比赛admin仍可管理自己的比赛。
+    // English: Contract-wide roles. Owner grants/revokes roles and implicitly passes every other role check.
+    //  A match admin can still operate their own match.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub enum Role {
+        Owner,         // 所有者 / Owner
+        MatchManager,  // 可开启/关闭任意比赛 / Can open/close any match
+        Resolver,      // 可结算任意比赛 / Can settle any match
+        Pauser,        // 可暂停/恢复 / Can pause/unpause
+        Treasurer,     // 可紧急提取盈余 / Can withdraw surplus in emergencies
+    }
+
+    // 结构体定义: 队伍 / Struct: Team
+    // 中文: 链上队伍注册表条目，客户端可直接通过 get_team 渲染名称，无需链下映射。
+    // English: On-chain team registry entry; clients render names through get_team without an off-chain mapping.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct TeamInfo {
+        pub id: TeamId,              // 队伍ID / Team ID
+        pub name: String,            // 名称 / Name
+        pub short_code: String,      // 简称（唯一，如 BRA） / Short code (unique, e.g. BRA)
+        pub country_code: String,    // 国家代码 / Country code
+        pub logo_cid: String,        // 队徽的IPFS CID / Logo IPFS CID
+    }
+
     // 结构体定义: 比赛 / Struct: Match
     // 中文: 存储每场比赛的信息，包括ID、admin、队伍等。初学者: #[derive] 添加了序列化支持，便于链上存储。
     // English: Stores information for each match, including ID, admin, teams, etc. For beginners: #[derive] adds serialization support for on-chain storage.
@@ -60,8 +154,8 @@ mod paragoal_betting {
     pub struct Match {
         pub id: u128,                // 比赛ID / Match ID
         pub admin: AccountId,        // 管理员地址（创建者） / Admin address (creator)
-        pub team_a: [u8; 32],        // 队伍A标识（bytes32） / Team A identifier (bytes32)
-        pub team_b: [u8; 32],        // 队伍B标识（bytes32） / Team B identifier (bytes32)
+        pub team_a: TeamId,          // 队伍A（注册表ID） / Team A (registry id)
+        pub team_b: TeamId,          // 队伍B（注册表ID） / Team B (registry id)
         pub is_built_in: bool,       // 是否内置比赛 / Is built-in match
         pub pool_injected_by: Option<AccountId>,  // 首次注入奖池的地址 / First pool injector address
         pub pool_amount: Balance,    // 奖池总额 / Total pool amount
@@ -69,11 +163,257 @@ mod paragoal_betting {
         pub result: MatchResult,     // 比赛结果 / Match result
         pub total_stake_a: Balance,  // TeamA总投注 / Total stake for TeamA
         pub total_stake_b: Balance,  // TeamB总投注 / Total stake for TeamB
+        pub paused: bool,            // 本场是否暂停 / Is this match paused
+        pub metadata: MatchMetadata, // 赛事元数据 / Fixture metadata
+        pub archived: bool,          // 是否已归档（不出现在默认列表） / Archived (hidden from default listings)
+    }
+
+    // 结构体定义: 比赛（存储版本3） / Struct: Match (storage v3)
+    // 中文: 冻结的旧布局，没有归档标记。
+    // English: Frozen legacy layout without the archived flag.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct MatchV3 {
+        pub id: u128,
+        pub admin: AccountId,
+        pub team_a: TeamId,
+        pub team_b: TeamId,
+        pub is_built_in: bool,
+        pub pool_injected_by: Option<AccountId>,
+        pub pool_amount: Balance,
+        pub status: MatchStatus,
+        pub result: MatchResult,
+        pub total_stake_a: Balance,
+        pub total_stake_b: Balance,
+        pub paused: bool,
+        pub metadata: MatchMetadata,
+    }
+
+    impl MatchV3 {
+        fn upgrade(self) -> Match {
+            Match {
+                id: self.id,
+                admin: self.admin,
+                team_a: self.team_a,
+                team_b: self.team_b,
+                is_built_in: self.is_built_in,
+                pool_injected_by: self.pool_injected_by,
+                pool_amount: self.pool_amount,
+                status: self.status,
+                result: self.result,
+                total_stake_a: self.total_stake_a,
+                total_stake_b: self.total_stake_b,
+                paused: self.paused,
+                metadata: self.metadata,
+                archived: false,
+            }
+        }
+    }
+
+    // 结构体定义: 比赛元数据 / Struct: Match Metadata
+    // 中文: 可选的赛事信息，创建时设置，Pending 状态下admin可修改。详细资料（海报、阵容等）放在 metadata_cid 指向的IPFS文档中。
+    // English: Optional fixture details set at creation and editable by the admin while Pending. Richer content
+    //  (artwork, line-ups, ...) lives in the IPFS document referenced by metadata_cid.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct MatchMetadata {
+        pub competition_id: Option<u32>,   // 赛事ID / Competition id
+        pub stage: Option<String>,         // 阶段（如 Quarter-final） / Stage (e.g. Quarter-final)
+        pub venue: Option<String>,         // 场地 / Venue label
+        pub kickoff: Option<Timestamp>,    // 开球时间（毫秒） / Kickoff timestamp (ms)
+        pub metadata_cid: Option<String>,  // 元数据内容哈希/CID / Metadata content hash or CID
+    }
+
+    // 结构体定义: 比赛（存储版本2） / Struct: Match (storage v2)
+    // 中文: 冻结的旧布局，没有元数据。
+    // English: Frozen legacy layout without metadata.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct MatchV2 {
+        pub id: u128,
+        pub admin: AccountId,
+        pub team_a: TeamId,
+        pub team_b: TeamId,
+        pub is_built_in: bool,
+        pub pool_injected_by: Option<AccountId>,
+        pub pool_amount: Balance,
+        pub status: MatchStatus,
+        pub result: MatchResult,
+        pub total_stake_a: Balance,
+        pub total_stake_b: Balance,
+        pub paused: bool,
+    }
+
+    impl MatchV2 {
+        fn upgrade(self) -> MatchV3 {
+            MatchV3 {
+                id: self.id,
+                admin: self.admin,
+                team_a: self.team_a,
+                team_b: self.team_b,
+                is_built_in: self.is_built_in,
+                pool_injected_by: self.pool_injected_by,
+                pool_amount: self.pool_amount,
+                status: self.status,
+                result: self.result,
+                total_stake_a: self.total_stake_a,
+                total_stake_b: self.total_stake_b,
+                paused: self.paused,
+                metadata: MatchMetadata::default(),
+            }
+        }
+    }
+
+    // 结构体定义: 比赛（存储版本1） / Struct: Match (storage v1)
+    // 中文: 冻结的旧布局，队伍为 bytes32 占位符。
+    // English: Frozen legacy layout with bytes32 team placeholders.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct MatchV1 {
+        pub id: u128,
+        pub admin: AccountId,
+        pub team_a: [u8; 32],
+        pub team_b: [u8; 32],
+        pub is_built_in: bool,
+        pub pool_injected_by: Option<AccountId>,
+        pub pool_amount: Balance,
+        pub status: MatchStatus,
+        pub result: MatchResult,
+        pub total_stake_a: Balance,
+        pub total_stake_b: Balance,
+        pub paused: bool,
+    }
+
+    impl MatchV1 {
+        // 旧占位符 [n; 32]（n < 8）对应内置队伍 n，其余标识无法识别 / Legacy [n; 32] (n < 8) maps to built-in team n
+        fn legacy_team(id: [u8; 32]) -> TeamId {
+            let first = id[0];
+            if (first as usize) < BUILT_IN_TEAMS.len() && id.iter().all(|b| *b == first) {
+                TeamId::from(first)
+            } else {
+                UNKNOWN_TEAM
+            }
+        }
+
+        fn upgrade(self) -> MatchV2 {
+            MatchV2 {
+                id: self.id,
+                admin: self.admin,
+                team_a: Self::legacy_team(self.team_a),
+                team_b: Self::legacy_team(self.team_b),
+                is_built_in: self.is_built_in,
+                pool_injected_by: self.pool_injected_by,
+                pool_amount: self.pool_amount,
+                status: self.status,
+                result: self.result,
+                total_stake_a: self.total_stake_a,
+                total_stake_b: self.total_stake_b,
+                paused: self.paused,
+            }
+        }
+    }
+
+    // 枚举定义: 带版本的比赛存储 / Enum: Versioned Match Record
+    // 中文: 比赛以带版本标签的形式存储，升级后旧版本记录在读取时即时转换为最新布局，migrate 再逐批写回。
+    //  新增布局时：把旧结构体冻结为 MatchVn，新增枚举变体，并在 into_latest 中补充转换。
+    // English: Matches are stored with a version tag. After an upgrade older records are converted to the latest layout
+    //  on read, and migrate rewrites them in batches. To change the layout: freeze the old struct as MatchVn, add a
+    //  variant and extend into_latest.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub enum VersionedMatch {
+        V1(MatchV1),
+        V2(MatchV2),
+        V3(MatchV3),
+        V4(Match),
+    }
+
+    impl VersionedMatch {
+        // 转换为最新布局 / Convert to the latest layout
+        pub fn into_latest(self) -> Match {
+            match self {
+                VersionedMatch::V1(m) => m.upgrade().upgrade().upgrade(),
+                VersionedMatch::V2(m) => m.upgrade().upgrade(),
+                VersionedMatch::V3(m) => m.upgrade(),
+                VersionedMatch::V4(m) => m,
+            }
+        }
+
+        // 是否已是最新布局 / Whether the record already uses the latest layout
+        pub fn is_latest(&self) -> bool {
+            matches!(self, VersionedMatch::V4(_))
+        }
+    }
+
+    // 结构体定义: 赛事 / Struct: Tournament
+    // 中文: 单败淘汰赛。首轮由已有比赛组成，之后每轮的比赛在上一轮两场比赛都产生胜者后自动创建。
+    // English: Single-elimination tournament. The first round groups existing matches; each later fixture is created
+    //  automatically once both feeding matches have a winner.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Tournament {
+        pub id: TournamentId,          // 赛事ID / Tournament ID
+        pub name: String,              // 名称 / Name
+        pub admin: AccountId,          // 赛事admin，也是后续轮次比赛的admin / Admin, also admin of later-round matches
+        pub rounds: u8,                // 轮次数 / Number of rounds
+        pub slot_count: u32,           // 对阵位数量（首轮比赛数*2-1） / Bracket slots (first-round matches * 2 - 1)
+        pub champion: Option<TeamId>,  // 冠军 / Champion
+    }
+
+    // 结构体定义: 对阵位 / Struct: Bracket Slot
+    // 中文: 对阵位按轮次顺序编号：首轮为 0..n，第二轮为 n..n+n/2，依此类推，决赛为最后一个。
+    //  对阵位 i 的胜者进入 n + i/2 号对阵位（i 为偶数时为A方，奇数时为B方）。
+    // English: Slots are numbered round by round: the first round is 0..n, the second n..n+n/2 and so on, with the
+    //  final last. The winner of slot i moves to slot n + i/2 (as team A for even i, team B for odd i).
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct BracketSlot {
+        pub slot: u32,                 // 对阵位编号 / Slot index
+        pub round: u8,                 // 轮次（0为首轮） / Round (0 is the first round)
+        pub match_id: Option<u128>,    // 对应比赛（双方确定后创建） / Match, created once both teams are known
+        pub team_a: Option<TeamId>,    // A方 / Team A
+        pub team_b: Option<TeamId>,    // B方 / Team B
+        pub winner: Option<TeamId>,    // 胜者 / Winner
+    }
+
+    // 结构体定义: 冠军盘 / Struct: Outright Market
+    // 中文: 赛事级“谁夺冠”市场，独立于单场投注，拥有自己的奖池。锁定时间前可投注任一参赛队伍。
+    //  结算规则与单场一致：所有人取回本金，押中冠军者按比例分奖池70%，其余投注者按比例分30%，有首次注入者时收5%手续费。
+    // English: Tournament-level "who wins it all" market, separate from per-match stakes and with its own pool. Any
+    //  participating team can be backed before the lock time. Settlement mirrors matches: everyone gets their
+    //  principal back, backers of the champion share 70% of the pool pro rata, everyone else shares 30%, and a 5% fee
+    //  applies when someone injected the pool first.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct OutrightMarket {
+        pub tournament_id: TournamentId,          // 赛事ID / Tournament ID
+        pub lock_time: Timestamp,                 // 投注截止时间 / Betting lock time
+        pub pool_amount: Balance,                 // 奖池总额 / Total pool amount
+        pub pool_injected_by: Option<AccountId>,  // 首次注入者（手续费接收者） / First injector (fee receiver)
+        pub total_staked: Balance,                // 投注总额 / Total staked
+        pub winner: Option<TeamId>,               // 结算冠军 / Settled champion
+    }
+
+    // 结构体定义: 冠军盘投注 / Struct: Outright Stake
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct OutrightStake {
+        pub team: TeamId,       // 投注队伍 / Backed team
+        pub amount: Balance,    // 投注金额 / Stake amount
+        pub claimed: bool,      // 是否已领取 / Has claimed
+    }
+
+    // 结构体定义: 待执行的升级 / Struct: Pending Upgrade
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct PendingUpgrade {
+        pub code_hash: Hash,   // 新代码哈希 / New code hash
+        pub eta: Timestamp,    // 最早可执行时间 / Earliest execution time
     }
 
     // 结构体定义: 用户投注记录 / Struct: Stake
-    // 中文: 记录用户的投注细节。初学者: Option<Balance> 表示可选值，如果未设置则为None。
-    // English: Records user's betting details. For beginners: Option<Balance> means optional value, None if not set.
+    // 中文: 记录用户在某场比赛某一方向的投注细节（按 (比赛, 用户, 队伍) 存储，允许双向对冲）。
+    // English: Records a user's position on one side of a match (stored per (match, user, team), so hedging is allowed).
     #[derive(scale::Encode, scale::Decode, Debug, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
     pub struct Stake {
@@ -82,151 +422,1008 @@ mod paragoal_betting {
         pub claimed: bool,      // 是否已领取 / Has claimed
     }
 
+    // 结构体定义: 投注限额 / Struct: Stake Limits
+    // 中文: 每场比赛的投注限额，由admin设置；None 表示不限制。max_total_pool 限制的是双方投注总额（不含注入奖池）。
+    // English: Per-match stake limits set by the admin; None means unlimited. max_total_pool caps the combined stakes
+    //  of both sides (injected pool excluded).
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct StakeLimits {
+        pub min_stake: Balance,                   // 单笔最小投注 / Minimum amount per stake call
+        pub max_stake_per_user: Option<Balance>,  // 单用户在本场的最大投注（双方合计） / Max per user in this match (both sides)
+        pub max_total_pool: Option<Balance>,      // 本场投注总额上限 / Max combined stakes in this match
+    }
+
+    // 结构体定义: 结算汇总 / Struct: Settlement Summary
+    // 中文: 结算时写入，领取时更新计数。平局时 winning_stake/losing_stake 分别为A方/B方投注。
+    //  预期金额按汇总计算，逐笔领取向下取整，实际领取总额可能略小（差额为尘埃）。
+    // English: Written at settlement, with the claim counters updated on every claim. For a draw winning_stake and
+    //  losing_stake hold the TeamA and TeamB stakes. Expected totals are computed in aggregate; individual claims
+    //  round down, so the claimed totals may end up slightly below them (dust).
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct SettlementSummary {
+        pub result: MatchResult,               // 结算结果 / Result
+        pub winning_stake: Balance,            // 赢方投注总额 / Winning side stake
+        pub losing_stake: Balance,             // 输方投注总额 / Losing side stake
+        pub pool_amount: Balance,              // 奖池总额 / Pool amount
+        pub fee_bps: u32,                      // 手续费率（基点） / Fee rate (bps)
+        pub expected_total_payout: Balance,    // 预期实付总额 / Expected total payout
+        pub expected_total_fees: Balance,      // 预期手续费总额 / Expected total fees
+        pub claimed_amount: Balance,           // 已付出金额 / Amount paid out so far
+        pub claimed_fees: Balance,             // 已收取手续费 / Fees collected so far
+        pub claimed_count: u32,                // 已领取人数 / Number of claims
+        pub settled_at: Timestamp,             // 结算时间 / Settlement time
+    }
+
+    // 结构体定义: 全局统计 / Struct: Global Stats
+    // 中文: get_global_stats 的返回值。资金计数同时覆盖单场比赛与冠军盘；各状态比赛数来自状态索引（已删除的比赛不计入）。
+    // English: Returned by get_global_stats. Volume counters cover both matches and outright markets; per-status match
+    //  counts come from the status indexes (deleted matches are not counted).
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct GlobalStats {
+        pub total_staked: Balance,     // 累计投注 / Total staked
+        pub total_injected: Balance,   // 累计注入奖池 / Total injected into pools
+        pub total_paid_out: Balance,   // 累计派奖（不含手续费） / Total paid out (fees excluded)
+        pub total_refunded: Balance,   // 累计提前撤注退款 / Total early-exit refunds
+        pub total_fees: Balance,       // 累计手续费 / Total fees
+        pub unique_bettors: u32,       // 独立投注账户数 / Unique bettors
+        pub pending_matches: u32,      // Pending 比赛数 / Pending matches
+        pub open_matches: u32,         // Open 比赛数 / Open matches
+        pub closed_matches: u32,       // Closed 比赛数 / Closed matches
+        pub settled_matches: u32,      // Settled 比赛数 / Settled matches
+    }
+
+    // 结构体定义: 全局资金计数 / Struct: Global Volume Counters
+    // 中文: 增量维护的存储部分，各状态比赛数在查询时从索引读取。
+    // English: The incrementally maintained part of GlobalStats; per-status counts are read from the indexes on query.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    struct StatsCounters {
+        total_staked: Balance,
+        total_injected: Balance,
+        total_paid_out: Balance,
+        total_refunded: Balance,
+        total_fees: Balance,
+        unique_bettors: u32,
+    }
+
+    // 结构体定义: 偿付能力报告 / Struct: Solvency Report
+    // 中文: audit_solvency 的返回值。surplus 为 余额 - 负债（不足时为0），solvent 表示余额是否覆盖负债。
+    // English: Returned by audit_solvency. surplus is balance minus liabilities (0 when short), solvent tells whether the
+    //  balance covers the liabilities.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SolvencyReport {
+        pub balance: Balance,      // 合约余额 / Contract balance
+        pub liabilities: Balance,  // 未偿负债 / Outstanding liabilities
+        pub surplus: Balance,      // 盈余 / Surplus
+        pub solvent: bool,         // 是否具备偿付能力 / Is solvent
+    }
+
+    // 枚举定义: 批量领取结果 / Enum: Claim Outcome
+    // 中文: claim_many 中每场比赛的处理结果；未结算、已领取或无法派发的比赛被跳过而不是让整个调用失败。
+    // English: Per-match result of claim_many; unsettled, already-claimed or unpayable matches are skipped instead of
+    //  aborting the call.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ClaimOutcome {
+        Paid(Balance),    // 已领取（扣费后金额） / Paid (amount after fee)
+        MatchNotFound,    // 比赛不存在 / Match not found
+        NotSettled,       // 未结算 / Not settled
+        NoStake,          // 无投注 / No stake
+        AlreadyClaimed,   // 已领取 / Already claimed
+        Paused,           // 比赛已暂停 / Match paused
+        Unpayable,        // 奖金无法计算（如溢出） / Payout cannot be computed (e.g. overflow)
+    }
+
+    // 结构体定义: 单笔投注记录 / Struct: Bet
+    // 中文: 每次调用 stake 都生成一条独立记录，保留下注时间与当时的隐含赔率；汇总金额仍在 Stake 中。
+    //  隐含赔率 = 若所押方向获胜时的回报倍数（扣费前）= (同向总额 + 70%奖池) / 同向总额，按下注后的池子状态计算。
+    // English: Every stake call creates its own record, keeping when it was placed and the implied odds at that moment;
+    //  the aggregate stays in Stake. Implied odds = return multiple if the side wins (before fee)
+    //  = (side total + 70% of pool) / side total, using the pool state right after placement.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Bet {
+        pub id: u64,                    // 投注ID / Bet ID
+        pub match_id: u128,             // 比赛ID / Match ID
+        pub user: AccountId,            // 投注者 / Bettor
+        pub team: Team,                 // 投注队伍 / Bet team
+        pub amount: Balance,            // 投注金额 / Bet amount
+        pub block_number: BlockNumber,  // 区块高度 / Block number
+        pub timestamp: Timestamp,       // 区块时间戳 / Block timestamp
+        pub implied_odds: u128,         // 隐含赔率（ODDS_PRECISION定点） / Implied odds (ODDS_PRECISION fixed-point)
+        pub withdrawn: Balance,         // 提前撤回的金额 / Amount withdrawn early
+    }
+
+    // 结构体定义: 用户持仓 / Struct: User Position
+    // 中文: get_user_positions 的返回项，汇总一场比赛的状态、用户投注与可领取金额。
+    // English: Item returned by get_user_positions, combining a match's status, the user's stake and pending payout.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UserPosition {
+        pub match_id: u128,           // 比赛ID / Match ID
+        pub status: MatchStatus,      // 比赛状态 / Match status
+        pub stakes: Vec<Stake>,       // 用户在各方向的投注 / User's positions on each side
+        pub pending_payout: Balance,  // 可领取金额 / Pending payout
+    }
+
+    // 枚举定义: 比赛二级索引 / Enum: Match Secondary Index
+    // 中文: 每个索引是一个可分页的比赛ID列表，在创建、状态变更与首次投注时维护。初学者: 枚举作为Mapping键，把多种索引放进同一组存储。
+    // English: Each index is a pageable list of match ids, maintained on create, status transitions and first stake.
+    //  For beginners: the enum is used as a Mapping key so all indexes share one set of storage mappings.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    enum MatchIndex {
+        All,                // 全部未归档比赛 / All matches not archived
+        Status(MatchStatus), // 按状态 / By status
+        Admin(AccountId),   // 按管理员 / By admin
+        BuiltIn(bool),      // 按是否内置 / By built-in flag
+        Bettor(AccountId),  // 用户投注过的比赛 / Matches an account has staked in
+        Archived,           // 已归档比赛 / Archived matches
+    }
+
     // 事件定义 / Events
     // 中文: Ink!事件用于通知链外（如前端）合约变化。初学者: #[ink(event)] 定义事件结构体。
     // English: Ink! events notify off-chain (e.g., frontend) of contract changes. For beginners: #[ink(event)] defines event structs.
     #[ink(event)]
     pub struct MatchCreated {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
+        #[ink(topic)]
         admin: AccountId,
-        team_a: [u8; 32],
-        team_b: [u8; 32],
+        team_a: TeamId,
+        team_b: TeamId,
         is_built_in: bool,
     }
 
+    #[ink(event)]
+    pub struct TeamRegistered {
+        team_id: TeamId,
+        short_code: String,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MatchMetadataUpdated {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        metadata: MatchMetadata,
+    }
+
+    #[ink(event)]
+    pub struct MatchTeamsUpdated {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        team_a: TeamId,
+        team_b: TeamId,
+    }
+
     #[ink(event)]
     pub struct PoolInjected {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
         from: AccountId,
         amount: Balance,
         total_pool: Balance,
+        fee_receiver: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct StakeLimitsUpdated {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        limits: StakeLimits,
     }
 
     #[ink(event)]
     pub struct Staked {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
+        #[ink(topic)]
         user: AccountId,
         team: Team,
         amount: Balance,
+        bet_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct StakeWithdrawn {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        #[ink(topic)]
+        user: AccountId,
+        team: Team,
+        amount: Balance,
+        penalty: Balance,
+    }
+
+    #[ink(event)]
+    pub struct MatchOpened {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        by: AccountId,
     }
 
     #[ink(event)]
     pub struct MatchClosed {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+    }
+
+    #[ink(event)]
+    pub struct MatchArchived {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+    }
+
+    #[ink(event)]
+    pub struct MatchDeleted {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
+        by: AccountId,
     }
 
     #[ink(event)]
     pub struct MatchSettled {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
         result: MatchResult,
+        winning_stake: Balance,
+        losing_stake: Balance,
+        pool_amount: Balance,
+        expected_total_payout: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TournamentCreated {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        #[ink(topic)]
+        admin: AccountId,
+        first_round: Vec<u128>,
+    }
+
+    #[ink(event)]
+    pub struct BracketAdvanced {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        slot: u32,
+        winner: TeamId,
+        next_match_id: Option<u128>,
+    }
+
+    #[ink(event)]
+    pub struct OutrightMarketOpened {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        lock_time: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct OutrightPoolInjected {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        from: AccountId,
+        amount: Balance,
+        total_pool: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OutrightStaked {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        #[ink(topic)]
+        user: AccountId,
+        team: TeamId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OutrightSettled {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        winner: TeamId,
+    }
+
+    #[ink(event)]
+    pub struct OutrightClaimed {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TournamentCompleted {
+        #[ink(topic)]
+        tournament_id: TournamentId,
+        champion: TeamId,
+    }
+
+    // 中文: match_id 为 None 表示全局暂停/恢复。
+    // English: A match_id of None means the global (contract-wide) switch.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        match_id: Option<u128>,
+        seq: Option<u64>,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        match_id: Option<u128>,
+        seq: Option<u64>,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleGranted {
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        previous_owner: AccountId,
+        pending_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SystemAdminUpdated {
+        #[ink(topic)]
+        previous_admin: AccountId,
+        #[ink(topic)]
+        new_admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferCancelled {
+        owner: AccountId,
+        pending_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        previous_owner: AccountId,
+        new_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MatchAdminTransferStarted {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        #[ink(topic)]
+        previous_admin: AccountId,
+        #[ink(topic)]
+        pending_admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MatchAdminTransferred {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        #[ink(topic)]
+        previous_admin: AccountId,
+        #[ink(topic)]
+        new_admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UpgradeProposed {
+        code_hash: Hash,
+        eta: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct UpgradeCancelled {
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Upgraded {
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Migrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyWithdrawal {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+        by: AccountId,
     }
 
     #[ink(event)]
     pub struct PayoutClaimed {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct UnclaimedWithdrawn {
+        #[ink(topic)]
         match_id: u128,
+        seq: u64,
+        #[ink(topic)]
         user: AccountId,
+        #[ink(topic)]
+        admin: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeePaid {
+        #[ink(topic)]
+        match_id: u128,
+        seq: u64,
+        #[ink(topic)]
+        receiver: AccountId,
         amount: Balance,
     }
 
+    #[ink(event)]
+    pub struct AccountExposureCapUpdated {
+        cap: Option<Balance>,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EarlyExitPenaltyUpdated {
+        penalty_bps: u32,
+        by: AccountId,
+    }
+
+    // 投注键 (match_id, user, team) / Stake key (match_id, user, team)
+    type StakeKey = (u128, AccountId, Team);
+    // 用户投注列表键 (match_id, user, index) / Per-user bet list key (match_id, user, index)
+    type UserBetKey = (u128, AccountId, u32);
+    type OutrightKey = (TournamentId, AccountId, TeamId);
+    type OutrightTeamKey = (TournamentId, AccountId, u32);
+
     // 合约存储 / Contract Storage
     // 中文: 所有持久化数据存储在这里。初学者: Mapping 类似于Solidity的mapping，用于键值存储。
+    //  升级注意: 根结构中的普通字段按顺序打包存储，升级后新增的标量字段必须使用 Lazy（或放入 Mapping），否则旧数据无法解码。
     // English: All persistent data is stored here. For beginners: Mapping is similar to Solidity's mapping for key-value storage.
+    //  Upgrade note: plain fields of the root struct are packed in order, so scalars added after an upgrade must use Lazy
+    //  (or live in a Mapping), otherwise existing state can no longer be decoded.
     #[ink(storage)]
     pub struct ParaGoalBetting {
         next_match_id: u128,                              // 下一个比赛ID / Next match ID
-        matches: Mapping<u128, Match>,                    // 比赛映射 / Matches mapping
-        stakes: Mapping<(u128, AccountId), Stake>,        // 投注记录 / Stakes mapping (match_id, user)
+        matches: Mapping<u128, VersionedMatch>,           // 比赛映射（带版本） / Matches mapping (versioned)
+        stakes: Mapping<StakeKey, Stake>,                 // 投注记录 / Stakes mapping (match_id, user, team)
         fee_receiver: Mapping<u128, AccountId>,           // 每个比赛的手续费接收者 / Fee receiver per match
-        deployer: AccountId,
+        stakers: Mapping<(u128, u32), AccountId>,         // 每场比赛的投注者列表 / Per-match staker list (match_id, index)
+        staker_counts: Mapping<u128, u32>,                // 每场比赛的投注者数量 / Staker count per match
+        index_entries: Mapping<(MatchIndex, u32), u128>,  // 索引条目 / Index entries (index, position) -> match_id
+        index_lens: Mapping<MatchIndex, u32>,             // 索引长度 / Index lengths
+        index_positions: Mapping<(MatchIndex, u128), u32>, // 比赛在索引中的位置 / Position of a match within an index
+        next_bet_id: u64,                                 // 下一个投注ID / Next bet ID
+        bets: Mapping<u64, Bet>,                          // 投注记录 / Bet records
+        user_bets: Mapping<UserBetKey, u64>,              // 用户在某场比赛的投注列表 / Per-user bet list (match_id, user, index)
+        user_bet_counts: Mapping<(u128, AccountId), u32>, // 用户在某场比赛的投注次数 / Per-user bet count
+        early_exit_penalty_bps: u32,                      // 提前撤注罚金（基点） / Early-exit penalty in basis points
+        stake_limits: Mapping<u128, StakeLimits>,         // 每场比赛的投注限额 / Stake limits per match
+        max_account_exposure: Option<Balance>,            // 单账户在所有Open比赛的投注上限 / Per-account cap across open matches
+        exposure_matches: Mapping<(AccountId, u32), u128>, // 账户可能仍为Open的已投注比赛 / Staked matches that may still be open
+        exposure_match_counts: Mapping<AccountId, u32>,   // 上述列表长度 / Length of that list
+        paused: bool,                                     // 全局暂停开关 / Global pause switch
+        roles: Mapping<(Role, AccountId), ()>,            // 角色成员 / Role membership
+        total_liabilities: Balance,                       // 合约对用户的未偿负债 / Outstanding liabilities to users
+        owner: AccountId,                                 // 主所有者（初始为部署者） / Primary owner (initially the deployer)
+        pending_owner: Option<AccountId>,                 // 待接受的新所有者 / Owner awaiting acceptance
+        pending_match_admins: Mapping<u128, AccountId>,   // 待接受的比赛admin / Match admins awaiting acceptance
+        storage_version: u32,                             // 已迁移完成的存储布局版本 / Storage layout version fully migrated to
+        migration_cursor: u128,                           // 迁移进度（下一个待检查的比赛ID） / Migration progress (next match id to check)
+        pending_upgrade: Option<PendingUpgrade>,          // 待执行的升级 / Pending upgrade
+        teams: Mapping<TeamId, TeamInfo>,                 // 队伍注册表 / Team registry
+        team_by_code: Mapping<String, TeamId>,            // 简称到队伍ID / Short code to team id
+        next_team_id: Lazy<TeamId>,                       // 下一个队伍ID（v2新增） / Next team id (added in v2)
+        tournaments: Mapping<TournamentId, Tournament>,   // 赛事 / Tournaments
+        bracket_slots: Mapping<(TournamentId, u32), BracketSlot>, // 对阵位 / Bracket slots
+        match_slots: Mapping<u128, (TournamentId, u32)>,  // 比赛所在对阵位 / Bracket slot of a match
+        next_tournament_id: Lazy<TournamentId>,           // 下一个赛事ID / Next tournament id
+        outright_markets: Mapping<TournamentId, OutrightMarket>, // 冠军盘 / Outright markets
+        outright_stakes: Mapping<OutrightKey, OutrightStake>, // 冠军盘投注 / Outright stakes (tournament, user, team)
+        outright_team_totals: Mapping<(TournamentId, TeamId), Balance>, // 冠军盘各队投注总额 / Outright total per team
+        outright_user_teams: Mapping<OutrightTeamKey, TeamId>, // 用户押注过的队伍 / Teams a user backed
+        outright_user_team_counts: Mapping<(TournamentId, AccountId), u32>, // 用户押注过的队伍数 / Count of teams a user backed
+        system_admin: Lazy<AccountId>,                    // 内置比赛的admin / Admin of built-in matches
+        match_event_seqs: Mapping<u128, u64>,             // 每场比赛已发出的事件序号 / Last event sequence number per match
+        settlement_summaries: Mapping<u128, SettlementSummary>, // 结算汇总 / Settlement summaries
+        stats: Lazy<StatsCounters>,                       // 全局资金计数 / Global volume counters
+        known_bettors: Mapping<AccountId, ()>,            // 投注过的账户 / Accounts that have ever staked
     }
 
-    // 为了满足某些严格的lint规则，提供Default实现（调用new）
-    // Provide Default to satisfy strict lints; internally calls new()
+    // 为了满足某些严格的lint规则，提供Default实现（调用new_empty）
+    // Provide Default to satisfy strict lints; internally calls new_empty()
     impl Default for ParaGoalBetting {
         fn default() -> Self {
-            Self::new()
+            Self::new_empty()
         }
     }
 
     impl ParaGoalBetting {
         // 构造函数 / Constructor
-        // 中文: 初始化合约，设置内置比赛（4场固定）。初学者: #[ink(constructor)] 表示这是合约部署时调用的函数。
-        // English: Initializes the contract, sets up built-in matches (4 fixed). For beginners: #[ink(constructor)] marks this as the deployment function.
+        // 中文: 初始化合约，按 fixtures 列表 (team_a, team_b, metadata) 创建内置比赛。system_admin 为内置比赛的admin，
+        //  未提供时为部署者。knockout 为 true 时，内置比赛按顺序组成淘汰赛赛事0（admin同为 system_admin），
+        //  此时比赛数须为2的幂且不超过 MAX_BRACKET_FIRST_ROUND。初学者: #[ink(constructor)] 表示这是合约部署时调用的函数。
+        // English: Initializes the contract and creates one built-in match per (team_a, team_b, metadata) fixture.
+        //  system_admin administers the built-in matches and defaults to the deployer. With `knockout` set, the
+        //  built-ins form knockout tournament 0 in order, also run by system_admin; the fixture count must then be a
+        //  power of two up to MAX_BRACKET_FIRST_ROUND. For beginners: #[ink(constructor)] marks this as the deployment
+        //  function.
+        #[ink(constructor)]
+        pub fn new(
+            fixtures: Vec<(TeamId, TeamId, MatchMetadata)>,
+            system_admin: Option<AccountId>,
+            knockout: bool,
+        ) -> Self {
+            let mut instance = Self::init(system_admin);
+            // 中文: 内置比赛不可删除。初学者: 这里循环创建比赛。
+            // English: Built-in matches can never be deleted. For beginners: Loop to create matches.
+            let first_round: Vec<u128> = fixtures
+                .into_iter()
+                .map(|(team_a, team_b, metadata)| instance.insert_built_in_match(team_a, team_b, metadata))
+                .collect();
+            // 按需把内置比赛组成赛事0（如世界杯1/4决赛） / Opt-in: the built-ins form tournament 0 (e.g. World Cup quarter-finals)
+            if knockout {
+                let size = first_round.len() as u32;
+                assert!(size >= 2 && size.is_power_of_two() && size <= MAX_BRACKET_FIRST_ROUND, "Invalid bracket size");
+                let admin = instance.system_admin();
+                instance.insert_tournament(admin, "Built-in Knockout".into(), first_round);
+            }
+            instance
+        }
+
+        // 构造函数: 空合约 / Constructor: Empty
+        // 中文: 不创建内置比赛，部署者为 system_admin；内置队伍仍会注册。
+        // English: Creates no built-in matches and makes the deployer system_admin; built-in teams are still registered.
         #[ink(constructor)]
-        pub fn new() -> Self {
+        pub fn new_empty() -> Self {
+            Self::init(None)
+        }
+
+        // 内部函数: 初始化存储 / Internal: Initialize Storage
+        // 中文: 两个构造函数共用：部署者成为Owner，注册内置队伍并记录 system_admin。
+        // English: Shared by both constructors: the deployer becomes Owner, built-in teams are registered and
+        //  system_admin is recorded.
+        fn init(system_admin: Option<AccountId>) -> Self {
             let caller = Self::env().caller();
             let mut instance = Self {
                 next_match_id: 0,
                 matches: Mapping::default(),
                 stakes: Mapping::default(),
                 fee_receiver: Mapping::default(),
-                deployer: caller,
+                stakers: Mapping::default(),
+                staker_counts: Mapping::default(),
+                index_entries: Mapping::default(),
+                index_lens: Mapping::default(),
+                index_positions: Mapping::default(),
+                next_bet_id: 0,
+                bets: Mapping::default(),
+                user_bets: Mapping::default(),
+                user_bet_counts: Mapping::default(),
+                early_exit_penalty_bps: DEFAULT_EARLY_EXIT_PENALTY_BPS,
+                stake_limits: Mapping::default(),
+                max_account_exposure: None,
+                exposure_matches: Mapping::default(),
+                exposure_match_counts: Mapping::default(),
+                paused: false,
+                roles: Mapping::default(),
+                total_liabilities: 0,
+                owner: caller,
+                pending_owner: None,
+                pending_match_admins: Mapping::default(),
+                storage_version: STORAGE_VERSION,
+                migration_cursor: 0,
+                pending_upgrade: None,
+                teams: Mapping::default(),
+                team_by_code: Mapping::default(),
+                next_team_id: Lazy::new(),
+                tournaments: Mapping::default(),
+                bracket_slots: Mapping::default(),
+                match_slots: Mapping::default(),
+                next_tournament_id: Lazy::new(),
+                outright_markets: Mapping::default(),
+                outright_stakes: Mapping::default(),
+                outright_team_totals: Mapping::default(),
+                outright_user_teams: Mapping::default(),
+                outright_user_team_counts: Mapping::default(),
+                system_admin: Lazy::new(),
+                match_event_seqs: Mapping::default(),
+                settlement_summaries: Mapping::default(),
+                stats: Lazy::new(),
+                known_bettors: Mapping::default(),
             };
-            // 初始化4场内置比赛 / Initialize 4 built-in matches
-            // 中文: 根据设计，内置4场固定比赛，admin设置为合约部署者。初学者: 这里循环创建比赛。
-            // English: As per design, 4 fixed built-in matches, admin set to deployer. For beginners: Loop to create matches.
-            let built_in_teams = vec![
-                ([0u8; 32], [1u8; 32]),  // 克罗地亚 vs 巴西 (placeholders) / Croatia vs Brazil
-                ([2u8; 32], [3u8; 32]),  // 荷兰 vs 阿根廷 / Netherlands vs Argentina
-                ([4u8; 32], [5u8; 32]),  // 摩洛哥 vs 葡萄牙 / Morocco vs Portugal
-                ([6u8; 32], [7u8; 32]),  // 英格兰 vs 法国 / England vs France
-            ];
-            for (team_a, team_b) in built_in_teams {
-                let match_id = instance.next_match_id;
-                instance.next_match_id += 1;
-                instance.matches.insert(match_id, &Match {
-                    id: match_id,
-                    admin: caller,  // 部署者为admin / Deployer as admin
-                    team_a,
-                    team_b,
-                    is_built_in: true,
-                    pool_injected_by: None,
-                    pool_amount: 0,
-                    status: MatchStatus::Pending,
-                    result: MatchResult::None,
-                    total_stake_a: 0,
-                    total_stake_b: 0,
-                });
-                instance.env().emit_event(MatchCreated {
-                    match_id,
-                    admin: caller,
-                    team_a,
-                    team_b,
-                    is_built_in: true,
-                });
-            }
+            instance.system_admin.set(&system_admin.unwrap_or(caller));
+            // 部署者成为Owner / Deployer becomes Owner
+            instance.roles.insert((Role::Owner, caller), &());
+            instance.env().emit_event(RoleGranted { role: Role::Owner, account: caller, by: caller });
+            // 注册内置队伍 / Register built-in teams
+            instance.register_built_in_teams(caller);
             instance
         }
 
         // 函数: 创建比赛 / Function: Create Match
         // 中文: 用户创建新比赛，调用者自动成为admin。初学者: #[ink(message)] 表示可外部调用，payable表示可接收资金（但这里不需）。
+        //  metadata 中的字段均为可选，提供时会发出 MatchMetadataUpdated 事件。
         // English: User creates a new match, caller becomes admin automatically. For beginners: #[ink(message)] makes it externally callable, payable allows receiving funds (not needed here).
+        //  All metadata fields are optional; when any is set a MatchMetadataUpdated event is emitted.
         #[ink(message)]
-        pub fn create_match(&mut self, team_a: [u8; 32], team_b: [u8; 32]) -> u128 {
-            let match_id = self.next_match_id;
-            self.next_match_id += 1;
+        pub fn create_match(&mut self, team_a: TeamId, team_b: TeamId, metadata: MatchMetadata) -> u128 {
+            self.ensure_not_paused();
+            self.ensure_valid_teams(team_a, team_b);
+            Self::ensure_valid_metadata(&metadata);
             let caller = self.env().caller();
-            self.matches.insert(match_id, &Match {
-                id: match_id,
-                admin: caller,  // 调用者即admin / Caller is admin
-                team_a,
-                team_b,
-                is_built_in: false,
-                pool_injected_by: None,
-                pool_amount: 0,
-                status: MatchStatus::Pending,
-                result: MatchResult::None,
-                total_stake_a: 0,
-                total_stake_b: 0,
+            let emit_metadata = metadata != MatchMetadata::default();
+            let match_id = self.insert_new_match(caller, team_a, team_b, false, metadata.clone());  // 调用者即admin / Caller is admin
+            if emit_metadata {
+                let seq = self.next_event_seq(match_id);
+                self.env().emit_event(MatchMetadataUpdated { match_id, seq, metadata });
+            }
+            self.debug_assert_solvent();
+            match_id
+        }
+
+        // 函数: 新增内置比赛 / Function: Add Built-in Match
+        // 中文: 仅Owner可调用，admin为 system_admin。内置比赛不可删除。
+        // English: Only an Owner can call; the match is administered by system_admin. Built-in matches can never be
+        //  deleted.
+        #[ink(message)]
+        pub fn add_built_in_match(&mut self, team_a: TeamId, team_b: TeamId, metadata: MatchMetadata) -> u128 {
+            self.ensure_not_paused();
+            self.ensure_role(Role::Owner);
+            let match_id = self.insert_built_in_match(team_a, team_b, metadata);
+            self.debug_assert_solvent();
+            match_id
+        }
+
+        // 函数: 设置内置比赛admin / Function: Set System Admin
+        // 中文: 仅Owner可调用。由旧 system_admin 管理的内置比赛一并转给新admin；之后新增的内置比赛也归新admin。
+        // English: Only an Owner can call. Built-in matches run by the previous system_admin move to the new one, as do
+        //  built-ins added later.
+        #[ink(message)]
+        pub fn set_system_admin(&mut self, new_admin: AccountId) {
+            self.ensure_role(Role::Owner);
+            self.replace_system_admin(new_admin);
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 内置比赛admin / View Function: System Admin
+        #[ink(message)]
+        pub fn system_admin(&self) -> AccountId {
+            self.system_admin.get().expect("System admin not set")
+        }
+
+        // 函数: 注册队伍 / Function: Register Team
+        // 中文: MatchManager（或Owner）可调用，返回新队伍ID。简称全局唯一，文本字段不超过 MAX_TEAM_FIELD_LEN 字节。
+        // English: MatchManager (or Owner) can call; returns the new team id. Short codes are globally unique and text
+        //  fields are limited to MAX_TEAM_FIELD_LEN bytes.
+        #[ink(message)]
+        pub fn register_team(&mut self, name: String, short_code: String, country_code: String, logo_cid: String) -> TeamId {
+            self.ensure_not_paused();
+            self.ensure_role(Role::MatchManager);
+            assert!(!name.is_empty() && !short_code.is_empty(), "Invalid team");
+            assert!(
+                [&name, &short_code, &country_code, &logo_cid].iter().all(|f| f.len() <= MAX_TEAM_FIELD_LEN),
+                "Team field too long"
+            );
+            assert!(!self.team_by_code.contains(&short_code), "Team code taken");
+            let caller = self.env().caller();
+            let team_id = self.insert_team(caller, name, short_code, country_code, logo_cid);
+            self.debug_assert_solvent();
+            team_id
+        }
+
+        // 查看函数: 获取队伍 / View Function: Get Team
+        #[ink(message)]
+        pub fn get_team(&self, team_id: TeamId) -> Option<TeamInfo> {
+            self.teams.get(&team_id)
+        }
+
+        // 查看函数: 按简称查找队伍 / View Function: Team by Short Code
+        #[ink(message)]
+        pub fn team_by_code(&self, short_code: String) -> Option<TeamId> {
+            self.team_by_code.get(&short_code)
+        }
+
+        // 查看函数: 队伍数量 / View Function: Team Count
+        #[ink(message)]
+        pub fn team_count(&self) -> TeamId {
+            self.next_team_id.get().unwrap_or(0)
+        }
+
+        // 函数: 创建赛事 / Function: Create Tournament
+        // 中文: 把调用者管理的若干场未结算比赛组成淘汰赛首轮（数量须为2的幂，不超过 MAX_BRACKET_FIRST_ROUND），
+        //  顺序决定对阵：第0、1场的胜者相遇，第2、3场的胜者相遇，依此类推。
+        // English: Groups unsettled matches administered by the caller into the first round of a knockout bracket (a
+        //  power of two, at most MAX_BRACKET_FIRST_ROUND). Order defines the pairing: winners of matches 0 and 1 meet,
+        //  then 2 and 3, and so on.
+        #[ink(message)]
+        pub fn create_tournament(&mut self, name: String, first_round: Vec<u128>) -> TournamentId {
+            self.ensure_not_paused();
+            assert!(!name.is_empty() && name.len() <= MAX_METADATA_FIELD_LEN, "Invalid tournament name");
+            let size = first_round.len() as u32;
+            assert!(size.is_power_of_two() && size <= MAX_BRACKET_FIRST_ROUND, "Invalid bracket size");
+            let caller = self.env().caller();
+            for (i, match_id) in first_round.iter().enumerate() {
+                let match_data = self.load_match(*match_id).expect("Match not found");
+                assert!(match_data.admin == caller, "Only admin");
+                assert!(match_data.status != MatchStatus::Settled, "Match already settled");
+                assert!(!self.match_slots.contains(match_id), "Match already in tournament");
+                assert!(!first_round[..i].contains(match_id), "Duplicate match");
+            }
+            let tournament_id = self.insert_tournament(caller, name, first_round);
+            self.debug_assert_solvent();
+            tournament_id
+        }
+
+        // 函数: 晋级胜者 / Function: Advance Winner
+        // 中文: 平局结算（如点球决胜）时由赛事admin或Resolver指定晋级队伍，须为该场比赛的双方之一。
+        // English: When a bracket match settles as a draw (e.g. decided on penalties), the tournament admin or a Resolver
+        //  names the team that advances; it must be one of the two sides.
+        #[ink(message)]
+        pub fn advance_winner(&mut self, tournament_id: TournamentId, slot: u32, winner: TeamId) {
+            self.ensure_not_paused();
+            let tournament = self.tournaments.get(&tournament_id).expect("Tournament not found");
+            self.ensure_tournament_admin_or_role(&tournament, Role::Resolver);
+            let entry = self.bracket_slots.get(&(tournament_id, slot)).expect("Slot not found");
+            assert!(entry.winner.is_none(), "Winner already set");
+            let match_data = self.load_match(entry.match_id.expect("Match not created")).expect("Match not found");
+            assert!(match_data.status == MatchStatus::Settled, "Not settled");
+            assert!(match_data.result == MatchResult::Draw, "Winner decided by result");
+            assert!(winner == match_data.team_a || winner == match_data.team_b, "Team not in match");
+            self.record_slot_winner(tournament_id, slot, winner);
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 获取赛事 / View Function: Get Tournament
+        #[ink(message)]
+        pub fn get_tournament(&self, tournament_id: TournamentId) -> Option<Tournament> {
+            self.tournaments.get(&tournament_id)
+        }
+
+        // 查看函数: 获取对阵表 / View Function: Get Bracket
+        // 中文: 按对阵位编号顺序返回全部对阵位（最多 2*MAX_BRACKET_FIRST_ROUND-1 个）。
+        // English: Returns every slot in slot order (at most 2 * MAX_BRACKET_FIRST_ROUND - 1).
+        #[ink(message)]
+        pub fn get_bracket(&self, tournament_id: TournamentId) -> Vec<BracketSlot> {
+            let Some(tournament) = self.tournaments.get(&tournament_id) else {
+                return Vec::new();
+            };
+            (0..tournament.slot_count)
+                .filter_map(|slot| self.bracket_slots.get(&(tournament_id, slot)))
+                .collect()
+        }
+
+        // 查看函数: 比赛所在对阵位 / View Function: Bracket Slot of a Match
+        #[ink(message)]
+        pub fn match_bracket_slot(&self, match_id: u128) -> Option<(TournamentId, u32)> {
+            self.match_slots.get(&match_id)
+        }
+
+        // 函数: 开设冠军盘 / Function: Open Outright Market
+        // 中文: 赛事admin或MatchManager可调用，每个赛事一个冠军盘，lock_time 须晚于当前时间。
+        //  须在对阵表中任何比赛结算之前开设，且 lock_time 不晚于首轮最早的开球时间（如已设置），避免在已知晋级结果后下注。
+        // English: The tournament admin or a MatchManager can call. One market per tournament; lock_time must be in the
+        //  future. The market must open before any bracket match settles, and lock_time may not be later than the
+        //  earliest first-round kickoff (where set), so nobody bets on teams already known to be out or through.
+        #[ink(message)]
+        pub fn open_outright_market(&mut self, tournament_id: TournamentId, lock_time: Timestamp) {
+            self.ensure_not_paused();
+            let tournament = self.tournaments.get(&tournament_id).expect("Tournament not found");
+            self.ensure_tournament_admin_or_role(&tournament, Role::MatchManager);
+            assert!(tournament.champion.is_none(), "Tournament completed");
+            assert!(!self.outright_markets.contains(tournament_id), "Market exists");
+            assert!(lock_time > self.env().block_timestamp(), "Invalid lock time");
+            let bracket_matches: Vec<Match> = (0..tournament.slot_count)
+                .filter_map(|slot| self.bracket_slots.get(&(tournament_id, slot)))
+                .filter_map(|entry| entry.match_id)
+                .filter_map(|match_id| self.load_match(match_id))
+                .collect();
+            assert!(
+                bracket_matches.iter().all(|m| m.status != MatchStatus::Settled),
+                "Bracket already started"
+            );
+            let first_kickoff = bracket_matches.iter().filter_map(|m| m.metadata.kickoff).min();
+            assert!(first_kickoff.is_none_or(|kickoff| lock_time <= kickoff), "Lock after kickoff");
+            self.outright_markets.insert(
+                tournament_id,
+                &OutrightMarket {
+                    tournament_id,
+                    lock_time,
+                    pool_amount: 0,
+                    pool_injected_by: None,
+                    total_staked: 0,
+                    winner: None,
+                },
+            );
+            self.env().emit_event(OutrightMarketOpened { tournament_id, lock_time });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 注入冠军盘奖池 / Function: Inject Outright Pool
+        // 中文: 结算前任何人可注入，首次注入者成为手续费接收者。
+        // English: Anyone can inject before settlement; the first injector becomes the fee receiver.
+        #[ink(message, payable)]
+        pub fn inject_outright_pool(&mut self, tournament_id: TournamentId) {
+            self.ensure_not_paused();
+            let injected = self.env().transferred_value();
+            assert!(injected > 0, "Injected amount must be >0");
+            let mut market = self.outright_markets.get(&tournament_id).expect("Market not found");
+            assert!(market.winner.is_none(), "Market settled");
+            let caller = self.env().caller();
+            market.pool_injected_by.get_or_insert(caller);
+            market.pool_amount = market.pool_amount.checked_add(injected).expect("Overflow");
+            self.outright_markets.insert(tournament_id, &market);
+            self.total_liabilities = self.total_liabilities.checked_add(injected).expect("Overflow");
+            self.update_stats(|stats| stats.total_injected = stats.total_injected.saturating_add(injected));
+            self.env().emit_event(OutrightPoolInjected {
+                tournament_id,
+                from: caller,
+                amount: injected,
+                total_pool: market.pool_amount,
             });
-            self.env().emit_event(MatchCreated {
-                match_id,
-                admin: caller,
-                team_a,
-                team_b,
-                is_built_in: false,
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 冠军盘投注 / Function: Stake Outright
+        // 中文: 锁定时间前可押注任一参赛队伍（首轮出现的队伍），可同时押注多支队伍。
+        // English: Before the lock time, back any participating team (one that appears in the first round); several
+        //  teams may be backed at once.
+        #[ink(message, payable)]
+        pub fn stake_outright(&mut self, tournament_id: TournamentId, team: TeamId) {
+            self.ensure_not_paused();
+            let amount = self.env().transferred_value();
+            assert!(amount > 0, "Stake must be >0");
+            let mut market = self.outright_markets.get(&tournament_id).expect("Market not found");
+            assert!(market.winner.is_none(), "Market settled");
+            assert!(self.env().block_timestamp() < market.lock_time, "Market locked");
+            assert!(self.is_tournament_team(tournament_id, team), "Team not in tournament");
+
+            let caller = self.env().caller();
+            let key = (tournament_id, caller, team);
+            let mut stake = self.outright_stakes.get(&key).unwrap_or_else(|| {
+                let count = self.outright_user_team_counts.get(&(tournament_id, caller)).unwrap_or(0);
+                self.outright_user_teams.insert((tournament_id, caller, count), &team);
+                self.outright_user_team_counts.insert((tournament_id, caller), &(count + 1));
+                OutrightStake { team, amount: 0, claimed: false }
             });
-            match_id
+            stake.amount = stake.amount.checked_add(amount).expect("Overflow");
+            self.outright_stakes.insert(key, &stake);
+            let team_total = self.outright_team_totals.get(&(tournament_id, team)).unwrap_or(0);
+            self.outright_team_totals
+                .insert((tournament_id, team), &team_total.checked_add(amount).expect("Overflow"));
+            market.total_staked = market.total_staked.checked_add(amount).expect("Overflow");
+            self.outright_markets.insert(tournament_id, &market);
+            self.total_liabilities = self.total_liabilities.checked_add(amount).expect("Overflow");
+            self.record_stake_volume(caller, amount);
+
+            self.env().emit_event(OutrightStaked { tournament_id, user: caller, team, amount });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 结算冠军盘 / Function: Settle Outright Market
+        // 中文: 决赛结算时自动执行；赛事无法正常完赛时，赛事admin或Resolver可在锁盘后手动指定冠军。
+        //  已产生冠军时只能确认该冠军；否则对阵表须卡住（比赛已不存在，或平局逾期未处理，见 is_bracket_stuck）。
+        // English: Runs automatically when the final settles; if the bracket cannot finish normally, the tournament
+        //  admin or a Resolver names the champion once the market is locked. If a champion exists it must be that
+        //  team; otherwise the bracket must be stuck (its match is gone, or a draw went unresolved; see
+        //  is_bracket_stuck).
+        #[ink(message)]
+        pub fn settle_outright(&mut self, tournament_id: TournamentId, winner: TeamId) {
+            self.ensure_not_paused();
+            let tournament = self.tournaments.get(&tournament_id).expect("Tournament not found");
+            self.ensure_tournament_admin_or_role(&tournament, Role::Resolver);
+            assert!(self.is_tournament_team(tournament_id, winner), "Team not in tournament");
+            let market = self.outright_markets.get(&tournament_id).expect("Market not found");
+            assert!(market.winner.is_none(), "Market settled");
+            assert!(self.env().block_timestamp() >= market.lock_time, "Market not locked");
+            match tournament.champion {
+                Some(champion) => assert!(winner == champion, "Winner is not champion"),
+                None => assert!(self.is_bracket_stuck(&tournament), "Bracket not stuck"),
+            }
+            self.finalize_outright(market, winner);
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 领取冠军盘奖金 / Function: Claim Outright Payout
+        #[ink(message)]
+        pub fn claim_outright(&mut self, tournament_id: TournamentId) -> Balance {
+            self.ensure_not_paused();
+            let caller = self.env().caller();
+            let stakes = self.get_outright_stakes(tournament_id, caller);
+            assert!(!stakes.is_empty(), "No stake");
+            assert!(stakes.iter().any(|s| !s.claimed), "Already claimed");
+            let market = self.outright_markets.get(&tournament_id).expect("Market not found");
+            assert!(market.winner.is_some(), "Not settled");
+
+            let (mut fee, mut payout): (Balance, Balance) = (0, 0);
+            for mut stake in stakes.into_iter().filter(|s| !s.claimed) {
+                let (f, p) = self.compute_outright_payout(&market, &stake);
+                fee = fee.checked_add(f).expect("Overflow");
+                payout = payout.checked_add(p).expect("Overflow");
+                stake.claimed = true;
+                self.outright_stakes.insert((tournament_id, caller, stake.team), &stake);
+            }
+            self.total_liabilities = self.total_liabilities.saturating_sub(fee + payout);
+            self.update_stats(|stats| {
+                stats.total_paid_out = stats.total_paid_out.saturating_add(payout);
+                stats.total_fees = stats.total_fees.saturating_add(fee);
+            });
+            if let (true, Some(receiver)) = (fee > 0, market.pool_injected_by) {
+                self.env().transfer(receiver, fee).expect("Fee transfer failed");
+            }
+            if payout > 0 {
+                self.env().transfer(caller, payout).expect("Transfer failed");
+            }
+            self.env().emit_event(OutrightClaimed { tournament_id, user: caller, amount: payout, fee });
+            self.debug_assert_solvent();
+            payout
+        }
+
+        // 查看函数: 获取冠军盘 / View Function: Get Outright Market
+        #[ink(message)]
+        pub fn get_outright_market(&self, tournament_id: TournamentId) -> Option<OutrightMarket> {
+            self.outright_markets.get(&tournament_id)
+        }
+
+        // 查看函数: 用户冠军盘投注 / View Function: User Outright Stakes
+        // 中文: 按首次押注顺序返回用户在该冠军盘的全部投注。
+        // English: Returns the user's outright stakes in the order the teams were first backed.
+        #[ink(message)]
+        pub fn get_outright_stakes(&self, tournament_id: TournamentId, user: AccountId) -> Vec<OutrightStake> {
+            let count = self.outright_user_team_counts.get(&(tournament_id, user)).unwrap_or(0);
+            (0..count)
+                .filter_map(|i| self.outright_user_teams.get(&(tournament_id, user, i)))
+                .filter_map(|team| self.outright_stakes.get(&(tournament_id, user, team)))
+                .collect()
+        }
+
+        // 查看函数: 冠军盘某队投注总额 / View Function: Outright Total for a Team
+        #[ink(message)]
+        pub fn outright_team_total(&self, tournament_id: TournamentId, team: TeamId) -> Balance {
+            self.outright_team_totals.get(&(tournament_id, team)).unwrap_or(0)
         }
 
         // 函数: 注入奖池 / Function: Inject Pool
@@ -236,7 +1433,8 @@ mod paragoal_betting {
         pub fn inject_pool(&mut self, match_id: u128) {
             let injected = self.env().transferred_value();
             assert!(injected > 0, "Injected amount must be >0");
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
             assert!(match_data.status != MatchStatus::Settled, "Cannot inject to settled match");
 
             if match_data.pool_injected_by.is_none() {
@@ -244,60 +1442,77 @@ mod paragoal_betting {
                 self.fee_receiver.insert(match_id, &self.env().caller());
             }
             match_data.pool_amount = match_data.pool_amount.checked_add(injected).expect("Overflow");
-            self.matches.insert(match_id, &match_data);
+            self.save_match(&match_data);
+            self.total_liabilities = self.total_liabilities.checked_add(injected).expect("Overflow");
+            self.update_stats(|stats| stats.total_injected = stats.total_injected.saturating_add(injected));
 
+            let seq = self.next_event_seq(match_id);
             self.env().emit_event(PoolInjected {
                 match_id,
+                seq,
                 from: self.env().caller(),
                 amount: injected,
                 total_pool: match_data.pool_amount,
+                fee_receiver: self.fee_receiver.get(&match_id).expect("Fee receiver set"),
             });
+            self.debug_assert_solvent();
         }
 
         // 函数: 开启比赛投注 / Function: Open Match
-        // 中文: 仅admin可调用，将状态从Pending变为Open。初学者: 使用assert! 检查权限和状态。
-        // English: Only admin can call, changes status from Pending to Open. For beginners: assert! checks permissions and status.
+        // 中文: 仅admin或MatchManager可调用，将状态从Pending变为Open。初学者: 使用assert! 检查权限和状态。
+        // English: Only admin or a MatchManager can call, changes status from Pending to Open. For beginners: assert! checks permissions and status.
         #[ink(message)]
         pub fn open_match(&mut self, match_id: u128) {
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
-            assert!(match_data.admin == self.env().caller(), "Only admin");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            self.ensure_admin_or_role(&match_data, Role::MatchManager);
             assert!(match_data.status == MatchStatus::Pending, "Not pending");
-            match_data.status = MatchStatus::Open;
-            self.matches.insert(match_id, &match_data);
-            // 无特定事件，但可添加 / No specific event, but can add if needed
+            self.set_status(&mut match_data, MatchStatus::Open);
+            self.save_match(&match_data);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchOpened { match_id, seq, by: self.env().caller() });
+            self.debug_assert_solvent();
         }
 
         // 函数: 关闭比赛投注 / Function: Close Match
-        // 中文: 仅admin可调用，将状态从Open变为Closed。
-        // English: Only admin can call, changes status from Open to Closed.
+        // 中文: 仅admin或MatchManager可调用，将状态从Open变为Closed。
+        // English: Only admin or a MatchManager can call, changes status from Open to Closed.
         #[ink(message)]
         pub fn close_match(&mut self, match_id: u128) {
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
-            assert!(match_data.admin == self.env().caller(), "Only admin");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            self.ensure_admin_or_role(&match_data, Role::MatchManager);
             assert!(match_data.status == MatchStatus::Open, "Not open");
-            match_data.status = MatchStatus::Closed;
-            self.matches.insert(match_id, &match_data);
-            self.env().emit_event(MatchClosed { match_id });
+            self.set_status(&mut match_data, MatchStatus::Closed);
+            self.save_match(&match_data);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchClosed { match_id, seq });
+            self.debug_assert_solvent();
         }
 
         // 函数: 投注 / Function: Stake
-        // 中文: 用户投注，选择队伍，更新总投注。初学者: payable接收投注金额。
-        // English: User stakes on a team, updates total stakes. For beginners: payable receives the stake amount.
+        // 中文: 用户投注，选择队伍，更新总投注，并生成一条独立的投注记录。返回投注ID。初学者: payable接收投注金额。
+        // English: User stakes on a team, updates total stakes and records a separate bet. Returns the bet id. For beginners: payable receives the stake amount.
         #[ink(message, payable)]
-        pub fn stake(&mut self, match_id: u128, team: Team) {
+        pub fn stake(&mut self, match_id: u128, team: Team) -> u64 {
             let amount = self.env().transferred_value();
             assert!(amount > 0, "Stake amount must be >0");
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
             assert!(match_data.status == MatchStatus::Open, "Match not open");
 
             let caller = self.env().caller();
-            let key = (match_id, caller);
-            let mut stake = self.stakes.get(&key).unwrap_or(Stake {
-                team,
-                amount: 0,
-                claimed: false,
-            });
-            assert!(stake.team == team, "Cannot change team");  // 防止切换队伍 / Prevent team switch
+            self.check_stake_limits(&match_data, caller, amount);
+            if !self.index_positions.contains(&(MatchIndex::Bettor(caller), match_id)) {
+                // 首次投注，加入参与者索引 / First stake, append to the participant index
+                let index = self.staker_counts.get(&match_id).unwrap_or(0);
+                self.stakers.insert((match_id, index), &caller);
+                self.staker_counts.insert(match_id, &(index + 1));
+                self.index_add(MatchIndex::Bettor(caller), match_id);
+            }
+            // 每个方向独立记录，允许对冲 / Each side is a separate position, so hedging is allowed
+            let key = (match_id, caller, team);
+            let mut stake = self.stakes.get(&key).unwrap_or(Stake { team, amount: 0, claimed: false });
             stake.amount = stake.amount.checked_add(amount).expect("Overflow");
             self.stakes.insert(key, &stake);
 
@@ -306,110 +1521,297 @@ mod paragoal_betting {
             } else {
                 match_data.total_stake_b = match_data.total_stake_b.checked_add(amount).expect("Overflow");
             }
-            self.matches.insert(match_id, &match_data);
+            self.save_match(&match_data);
+            self.total_liabilities = self.total_liabilities.checked_add(amount).expect("Overflow");
+            self.track_exposure(caller, match_id);
+            self.record_stake_volume(caller, amount);
 
+            let bet_id = self.record_bet(&match_data, caller, team, amount);
+            let seq = self.next_event_seq(match_id);
             self.env().emit_event(Staked {
                 match_id,
+                seq,
                 user: caller,
                 team,
                 amount,
+                bet_id,
             });
+            self.debug_assert_solvent();
+            bet_id
         }
 
-        // 函数: 结算比赛 / Function: Settle Match
-        // 中文: 仅admin可调用，设置结果并更改状态为Settled。
-        // English: Only admin can call, sets result and changes status to Settled.
+        // 函数: 设置投注限额 / Function: Set Stake Limits
+        // 中文: 仅admin可调用，在比赛结束投注前（Pending/Open）设置本场的投注限额，只约束之后的投注。
+        // English: Only admin can call, while the match is Pending or Open; the limits apply to subsequent stakes only.
         #[ink(message)]
-        pub fn settle_match(&mut self, match_id: u128, result: MatchResult) {
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
+        pub fn set_stake_limits(&mut self, match_id: u128, limits: StakeLimits) {
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
             assert!(match_data.admin == self.env().caller(), "Only admin");
-            assert!(match_data.status == MatchStatus::Closed, "Not closed");
-            assert!(result != MatchResult::None, "Invalid result");
-            match_data.result = result;
-            match_data.status = MatchStatus::Settled;
-            self.matches.insert(match_id, &match_data);
-            self.env().emit_event(MatchSettled { match_id, result });
+            assert!(
+                matches!(match_data.status, MatchStatus::Pending | MatchStatus::Open),
+                "Betting already closed"
+            );
+            assert!(
+                limits.max_stake_per_user.is_none_or(|max| limits.min_stake <= max),
+                "Invalid stake limits"
+            );
+            self.stake_limits.insert(match_id, &limits);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(StakeLimitsUpdated { match_id, seq, limits });
+            self.debug_assert_solvent();
         }
 
-        // 函数: 领取奖金 / Function: Claim Payout
-        // 中文: 用户领取结算后的奖金，使用结算算法。初学者: 这里实现防重入（通过claimed标志），计算比例并转账。
-        // English: User claims payout after settlement, using the allocation algorithm. For beginners: Implements reentrancy guard via claimed flag, calculates ratios and transfers.
+        // 查看函数: 投注限额 / View Function: Stake Limits
         #[ink(message)]
-        pub fn claim_payout(&mut self, match_id: u128) {
+        pub fn get_stake_limits(&self, match_id: u128) -> StakeLimits {
+            self.stake_limits.get(&match_id).unwrap_or_default()
+        }
+
+        // 函数: 设置账户敞口上限 / Function: Set Account Exposure Cap
+        // 中文: 仅Owner可调用，限制单个账户在所有Open比赛中的投注总额；None 表示不限制。
+        // English: Only an Owner can call; caps an account's combined stakes across all open matches. None disables the cap.
+        #[ink(message)]
+        pub fn set_max_account_exposure(&mut self, cap: Option<Balance>) {
+            self.ensure_not_paused();
+            self.ensure_role(Role::Owner);
+            self.max_account_exposure = cap;
+            self.env().emit_event(AccountExposureCapUpdated { cap, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 账户敞口上限 / View Function: Account Exposure Cap
+        #[ink(message)]
+        pub fn max_account_exposure(&self) -> Option<Balance> {
+            self.max_account_exposure
+        }
+
+        // 查看函数: 账户当前敞口 / View Function: Account Exposure
+        // 中文: 返回账户在所有Open比赛中的投注总额，比赛关闭或结算后即不再计入。
+        //  只遍历账户的候选列表（每次投注时剔除已非Open的比赛），开销不随历史投注增长。
+        // English: Returns the account's combined stakes across all open matches; a match stops counting once it is
+        //  closed or settled. Only the account's candidate list is walked (matches no longer open are pruned on every
+        //  stake), so the cost does not grow with betting history.
+        #[ink(message)]
+        pub fn account_exposure(&self, user: AccountId) -> Balance {
+            let len = self.exposure_match_counts.get(&user).unwrap_or(0);
+            (0..len)
+                .filter_map(|index| self.exposure_matches.get(&(user, index)))
+                .filter(|match_id| self.load_match(*match_id).is_some_and(|m| m.status == MatchStatus::Open))
+                .flat_map(|match_id| self.user_stakes(match_id, user))
+                .fold(0, |total: Balance, stake| total.saturating_add(stake.amount))
+        }
+
+        // 函数: 提前撤注 / Function: Withdraw Stake
+        // 中文: 仅在比赛Open时可撤回某一方向的部分或全部投注，按 early_exit_penalty_bps 扣除罚金，罚金并入奖池。返回实际退还金额。
+        // English: Withdraws part or all of the position on `team` while the match is Open. A penalty of
+        //  early_exit_penalty_bps is kept and added to the pool. Returns the amount refunded.
+        #[ink(message)]
+        pub fn withdraw_stake(&mut self, match_id: u128, team: Team, amount: Balance) -> Balance {
+            assert!(amount > 0, "Withdraw amount must be >0");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.status == MatchStatus::Open, "Match not open");
+
             let caller = self.env().caller();
-            let key = (match_id, caller);
+            let key = (match_id, caller, team);
             let mut stake = self.stakes.get(&key).expect("No stake");
-            assert!(!stake.claimed, "Already claimed");
-            let match_data = self.matches.get(&match_id).expect("Match not found");
-            assert!(match_data.status == MatchStatus::Settled, "Not settled");
-
-            let is_winner = if match_data.result == MatchResult::TeamA {
-                stake.team == Team::TeamA
-            } else if match_data.result == MatchResult::Draw {
-                stake.team == Team::TeamA || stake.team == Team::TeamB
+            stake.amount = stake.amount.checked_sub(amount).expect("Insufficient stake");
+            if stake.amount == 0 {
+                // 全部撤回即了结该方向，不留零额持仓 / A fully withdrawn side is resolved; no zero-amount position remains
+                self.stakes.remove(key);
             } else {
-                stake.team == Team::TeamB
-            };
+                self.stakes.insert(key, &stake);
+            }
+            self.record_bet_withdrawal(match_id, caller, team, amount);
 
-            let total_stake_team = if stake.team == Team::TeamA {
-                match_data.total_stake_a
-            } else if stake.team == Team::TeamB {
-                match_data.total_stake_b
+            if team == Team::TeamA {
+                match_data.total_stake_a -= amount;
             } else {
-                0 // Draw, total stake is 0
-            };
-            assert!(total_stake_team > 0, "No stakes for team");
+                match_data.total_stake_b -= amount;
+            }
+            let penalty = amount
+                .checked_mul(self.early_exit_penalty_bps as Balance)
+                .expect("Overflow")
+                / BPS_DENOMINATOR as Balance;
+            match_data.pool_amount = match_data.pool_amount.checked_add(penalty).expect("Overflow");
+            self.save_match(&match_data);
 
-            let pool_share = if match_data.result == MatchResult::Draw {
-                (match_data.pool_amount * 50) / 100  // 平分 / 50% split
-            } else if is_winner {
-                (match_data.pool_amount * 70) / 100
-            } else {
-                (match_data.pool_amount * 30) / 100
-            };
-            let user_pool: Balance = if total_stake_team == 0 {
-                0 // 无同向总额，则仅返还本金 / No same-side total, return principal only
-            } else {
-                stake
-                    .amount
-                    .checked_mul(pool_share)
-                    .expect("Overflow")
-                    / total_stake_team
-            };
-            let user_share = stake.amount.checked_add(user_pool).expect("Overflow");
+            // 罚金留在奖池中，仍属负债 / The penalty stays in the pool and remains a liability
+            let refund = amount - penalty;
+            self.total_liabilities = self.total_liabilities.saturating_sub(refund);
+            self.update_stats(|stats| stats.total_refunded = stats.total_refunded.saturating_add(refund));
+            self.env().transfer(caller, refund).expect("Transfer failed");
 
-            // 手续费接收者判定在转账前 / Decide fee before transfers
-            let fee_receiver = self.fee_receiver.get(&match_id);
-            let (fee, payout) = if fee_receiver.is_some() {
-                let f = user_share.checked_mul(5).expect("Overflow") / 100;
-                let p = user_share.checked_sub(f).expect("Underflow");
-                (f, p)
-            } else {
-                // 无首次注入者，则不收取手续费 / No first injector -> no fee
-                (0, user_share)
-            };
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(StakeWithdrawn {
+                match_id,
+                seq,
+                user: caller,
+                team,
+                amount,
+                penalty,
+            });
+            self.debug_assert_solvent();
+            refund
+        }
+
+        // 函数: 设置提前撤注罚金 / Function: Set Early-Exit Penalty
+        // 中文: 仅Owner可调用，罚金以基点表示，最多为 BPS_DENOMINATOR（100%）。
+        // English: Only an Owner can call; the penalty is in basis points, at most BPS_DENOMINATOR (100%).
+        #[ink(message)]
+        pub fn set_early_exit_penalty(&mut self, penalty_bps: u32) {
+            self.ensure_not_paused();
+            self.ensure_role(Role::Owner);
+            assert!(penalty_bps <= BPS_DENOMINATOR, "Invalid penalty");
+            self.early_exit_penalty_bps = penalty_bps;
+            self.env().emit_event(EarlyExitPenaltyUpdated { penalty_bps, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
 
-            // 转账给用户 / Transfer to user
-            self.env().transfer(caller, payout).expect("Transfer failed");
+        // 查看函数: 提前撤注罚金 / View Function: Early-Exit Penalty
+        #[ink(message)]
+        pub fn early_exit_penalty_bps(&self) -> u32 {
+            self.early_exit_penalty_bps
+        }
 
-            // 如有手续费接收者则发送手续费 / Send fee if receiver exists
-            if let Some(receiver) = fee_receiver {
-                if fee > 0 {
-                    self
-                        .env()
-                        .transfer(receiver, fee)
-                        .expect("Fee transfer failed");
+        // 函数: 结算比赛 / Function: Settle Match
+        // 中文: 仅admin或Resolver可调用，设置结果并更改状态为Settled。
+        // English: Only admin or a Resolver can call, sets result and changes status to Settled.
+        #[ink(message)]
+        pub fn settle_match(&mut self, match_id: u128, result: MatchResult) {
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            self.ensure_admin_or_role(&match_data, Role::Resolver);
+            assert!(match_data.status == MatchStatus::Closed, "Not closed");
+            assert!(result != MatchResult::None, "Invalid result");
+            match_data.result = result;
+            self.set_status(&mut match_data, MatchStatus::Settled);
+            self.save_match(&match_data);
+            // 无人投注一方的奖池份额无人可领，不再计为负债 / A side with no stakes can never claim its pool share
+            let unowed = self.unclaimable_pool_share(&match_data);
+            self.total_liabilities = self.total_liabilities.saturating_sub(unowed);
+            let summary = self.build_settlement_summary(&match_data, unowed);
+            self.settlement_summaries.insert(match_id, &summary);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchSettled {
+                match_id,
+                seq,
+                result,
+                winning_stake: summary.winning_stake,
+                losing_stake: summary.losing_stake,
+                pool_amount: summary.pool_amount,
+                expected_total_payout: summary.expected_total_payout,
+            });
+            // 淘汰赛: 分出胜负时自动晋级 / Knockout: advance automatically on a decisive result
+            if let Some((tournament_id, slot)) = self.match_slots.get(&match_id) {
+                match result {
+                    MatchResult::TeamA => self.record_slot_winner(tournament_id, slot, match_data.team_a),
+                    MatchResult::TeamB => self.record_slot_winner(tournament_id, slot, match_data.team_b),
+                    _ => {}
                 }
             }
+            self.debug_assert_solvent();
+        }
 
-            stake.claimed = true;
-            self.stakes.insert(key, &stake);
+        // 函数: 领取奖金 / Function: Claim Payout
+        // 中文: 用户领取结算后的奖金，汇总其在该比赛所有方向的投注，使用结算算法。初学者: 这里实现防重入（通过claimed标志），计算比例并转账。
+        // English: User claims payout after settlement, aggregated across all of their positions in the match. For beginners: Implements reentrancy guard via claimed flag, calculates ratios and transfers.
+        #[ink(message)]
+        pub fn claim_payout(&mut self, match_id: u128) {
+            let caller = self.env().caller();
+            let stakes = self.user_stakes(match_id, caller);
+            assert!(!stakes.is_empty(), "No stake");
+            assert!(stakes.iter().any(|s| !s.claimed), "Already claimed");
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.status == MatchStatus::Settled, "Not settled");
+
+            let payout = self.pay_out(&match_data, caller, caller);
 
+            let seq = self.next_event_seq(match_id);
             self.env().emit_event(PayoutClaimed {
                 match_id,
+                seq,
                 user: caller,
                 amount: payout,
             });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 批量派发奖金 / Function: Distribute Payouts
+        // 中文: 结算后任何人都可调用，为列表中的用户各自发放其奖金到其本人地址；已领取或无投注的用户被跳过。
+        //  单批次用户数上限为 MAX_DISTRIBUTE_BATCH，确保交易不超出区块权重。返回实际派发的用户数。
+        // English: Callable by anyone after settlement; pays each listed user their own payout to their own account, skipping
+        //  users who already claimed or have no stake. Batches are capped at MAX_DISTRIBUTE_BATCH to fit block weight limits.
+        //  Returns the number of users actually paid.
+        #[ink(message)]
+        pub fn distribute(&mut self, match_id: u128, users: Vec<AccountId>) -> u32 {
+            assert!(users.len() <= MAX_DISTRIBUTE_BATCH, "Batch too large");
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.status == MatchStatus::Settled, "Not settled");
+
+            let mut paid = 0u32;
+            for user in users {
+                if !self.user_stakes(match_id, user).iter().any(|s| !s.claimed) {
+                    continue; // 无投注或已领取 / No stake or already claimed
+                }
+                let payout = self.pay_out(&match_data, user, user);
+                let seq = self.next_event_seq(match_id);
+                self.env().emit_event(PayoutClaimed {
+                    match_id,
+                    seq,
+                    user,
+                    amount: payout,
+                });
+                paid += 1;
+            }
+            self.debug_assert_solvent();
+            paid
+        }
+
+        // 函数: 批量领取奖金 / Function: Claim Many
+        // 中文: 一次领取多场比赛的奖金，所有可领取金额合并为一笔转账；未结算、无投注、已领取或无法派发的比赛被跳过。
+        //  返回每场比赛的处理结果列表。
+        // English: Claims payouts across several matches, aggregating everything claimable into a single transfer;
+        //  unsettled, unstaked, already-claimed or unpayable matches are skipped. Returns a per-match result list.
+        #[ink(message)]
+        pub fn claim_many(&mut self, match_ids: Vec<u128>) -> Vec<(u128, ClaimOutcome)> {
+            assert!(match_ids.len() <= MAX_CLAIM_BATCH, "Batch too large");
+            self.ensure_not_paused();
+            let caller = self.env().caller();
+            let mut total: Balance = 0;
+            let mut outcomes = Vec::with_capacity(match_ids.len());
+            for match_id in match_ids {
+                let stakes = self.user_stakes(match_id, caller);
+                let outcome = match self.load_match(match_id) {
+                    None => ClaimOutcome::MatchNotFound,
+                    Some(m) if m.paused => ClaimOutcome::Paused,
+                    Some(m) if m.status != MatchStatus::Settled => ClaimOutcome::NotSettled,
+                    Some(_) if stakes.is_empty() => ClaimOutcome::NoStake,
+                    Some(_) if stakes.iter().all(|s| s.claimed) => ClaimOutcome::AlreadyClaimed,
+                    Some(m) if !self.is_payable(&m, &stakes) => ClaimOutcome::Unpayable,
+                    Some(m) => {
+                        let payout = self.take_payout(&m, caller);
+                        total = total.checked_add(payout).expect("Overflow");
+                        let seq = self.next_event_seq(match_id);
+                        self.env().emit_event(PayoutClaimed {
+                            match_id,
+                            seq,
+                            user: caller,
+                            amount: payout,
+                        });
+                        ClaimOutcome::Paid(payout)
+                    }
+                };
+                outcomes.push((match_id, outcome));
+            }
+            if total > 0 {
+                self.env().transfer(caller, total).expect("Transfer failed");
+            }
+            self.debug_assert_solvent();
+            outcomes
         }
 
         // 新函数: 提取未领取奖励 / Function: Withdraw Unclaimed
@@ -417,79 +1819,20 @@ mod paragoal_betting {
         // English: Only admin can call, withdraws unclaimed payout for a user to admin address (prevent locked funds). For beginners: This is an optional recovery mechanism, valid only after Settled.
         #[ink(message)]
         pub fn withdraw_unclaimed(&mut self, match_id: u128, user: AccountId) {
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
             assert!(match_data.admin == self.env().caller(), "Only admin");
             assert!(match_data.status == MatchStatus::Settled, "Not settled");
 
-            let key = (match_id, user);
-            let mut stake = self.stakes.get(&key).expect("No stake");
-            assert!(!stake.claimed, "Already claimed");
-
-            // 计算用户份额（同claim_payout逻辑） / Calculate user share (same as claim_payout)
-            let is_winner = if match_data.result == MatchResult::TeamA {
-                stake.team == Team::TeamA
-            } else if match_data.result == MatchResult::Draw {
-                stake.team == Team::TeamA || stake.team == Team::TeamB
-            } else {
-                stake.team == Team::TeamB
-            };
-
-            let total_stake_team = if stake.team == Team::TeamA {
-                match_data.total_stake_a
-            } else if stake.team == Team::TeamB {
-                match_data.total_stake_b
-            } else {
-                0 // Draw, total stake is 0
-            };
-            assert!(total_stake_team > 0, "No stakes for team");
-
-            let user_pool: Balance = if total_stake_team == 0 {
-                0
-            } else {
-                let share = if match_data.result == MatchResult::Draw {
-                    (match_data.pool_amount * 50) / 100
-                } else if is_winner {
-                    (match_data.pool_amount * 70) / 100
-                } else {
-                    (match_data.pool_amount * 30) / 100
-                };
-                stake
-                    .amount
-                    .checked_mul(share)
-                    .expect("Overflow")
-                    / total_stake_team
-            };
-            let user_share = stake.amount.checked_add(user_pool).expect("Overflow");
-
-            let fee_receiver = self.fee_receiver.get(&match_id);
-            let (fee, payout) = if fee_receiver.is_some() {
-                let f = user_share.checked_mul(5).expect("Overflow") / 100;
-                let p = user_share.checked_sub(f).expect("Underflow");
-                (f, p)
-            } else {
-                (0, user_share)
-            };
-
-            // 转账到管理员（而非用户） / Transfer to admin (instead of user)
-            self
-                .env()
-                .transfer(match_data.admin, payout)
-                .expect("Transfer failed");
-
-            // 手续费仍给接收者（若存在） / Fee still to receiver if exists
-            if let Some(receiver) = fee_receiver {
-                if fee > 0 {
-                    self
-                        .env()
-                        .transfer(receiver, fee)
-                        .expect("Fee transfer failed");
-                }
-            }
-
-            stake.claimed = true;  // 标记为已处理 / Mark as handled
-            self.stakes.insert(key, &stake);
+            let stakes = self.user_stakes(match_id, user);
+            assert!(!stakes.is_empty(), "No stake");
+            assert!(stakes.iter().any(|s| !s.claimed), "Already claimed");
 
-            // 可添加事件 / Can add event if needed
+            // 转账到管理员（而非用户），手续费仍给接收者 / Transfer to admin (instead of user), fee still to receiver
+            let amount = self.pay_out(&match_data, user, match_data.admin);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(UnclaimedWithdrawn { match_id, seq, user, admin: match_data.admin, amount });
+            self.debug_assert_solvent();
         }
 
         // 查看函数: 获取比赛信息 / View Function: Get Match
@@ -497,139 +1840,2705 @@ mod paragoal_betting {
         // English: Read-only function, returns match details. For beginners: No 'mut' means it's a view.
         #[ink(message)]
         pub fn get_match(&self, match_id: u128) -> Option<Match> {
-            self.matches.get(&match_id)
+            self.load_match(match_id)
         }
 
         // 查看函数: 获取用户投注 / View Function: Get User Stake
+        // 中文: 返回用户在该比赛的全部方向投注（TeamA在前），无投注时为空列表。
+        // English: Returns every position the user holds in the match (TeamA first), empty if none.
         #[ink(message)]
-        pub fn get_user_stake(&self, match_id: u128, user: AccountId) -> Option<Stake> {
-            self.stakes.get(&(match_id, user))
+        pub fn get_user_stake(&self, match_id: u128, user: AccountId) -> Vec<Stake> {
+            self.user_stakes(match_id, user)
         }
 
-        // 其他函数可根据需要添加 / Additional functions can be added as needed
+        // 查看函数: 全局统计 / View Function: Global Stats
+        #[ink(message)]
+        pub fn get_global_stats(&self) -> GlobalStats {
+            let counters = self.stats.get().unwrap_or_default();
+            let count = |status| self.index_lens.get(&MatchIndex::Status(status)).unwrap_or(0);
+            GlobalStats {
+                total_staked: counters.total_staked,
+                total_injected: counters.total_injected,
+                total_paid_out: counters.total_paid_out,
+                total_refunded: counters.total_refunded,
+                total_fees: counters.total_fees,
+                unique_bettors: counters.unique_bettors,
+                pending_matches: count(MatchStatus::Pending),
+                open_matches: count(MatchStatus::Open),
+                closed_matches: count(MatchStatus::Closed),
+                settled_matches: count(MatchStatus::Settled),
+            }
+        }
 
-        // 查看函数: 预览可领取金额 / View Function: Pending Payout Preview
-        // 中文: 返回用户在指定比赛的“可领取最终金额”（扣费后），不改状态、不转账。\n
-        //  - 若比赛未结算/用户无下注/已领取，返回0。\n
-        //  - 计算与 claim_payout 完全一致：本金 + 按方向分配（赢70%/输30%/平局50%），再扣5%手续费（若有首次注入者）。
-        // English: Returns user's final payable amount for a given match (after fees), read-only.\n
-        //  - Returns 0 if not settled / no stake / already claimed.\n
-        //  - Same math as claim_payout: principal + side share (win 70% / lose 30% / draw 50%), minus 5% fee if a fee receiver exists.
+        // 查看函数: 结算汇总 / View Function: Settlement Summary
+        // 中文: 未结算的比赛返回 None。剩余未领取金额 = expected_total_payout - claimed_amount。
+        // English: None until the match settles. Remaining unclaimed = expected_total_payout - claimed_amount.
         #[ink(message)]
-        pub fn pending_payout(&self, match_id: u128, user: AccountId) -> Balance {
-            // 读取用户投注 / Load user stake (or 0 if missing)
-            let stake = match self.stakes.get(&(match_id, user)) {
-                Some(s) => s,
-                None => return 0,
-            };
-            // 已领取直接返回0 / Already claimed -> zero
-            if stake.claimed { return 0; }
-            // 读取比赛并校验状态 / Load match and ensure it's settled
-            let m = match self.matches.get(&match_id) {
-                Some(mm) => mm,
-                None => return 0,
-            };
-            if m.status != MatchStatus::Settled { return 0; }
+        pub fn get_settlement_summary(&self, match_id: u128) -> Option<SettlementSummary> {
+            self.settlement_summaries.get(&match_id)
+        }
 
-            // 计算分配方向 / Determine allocation side
-            let is_winner = if m.result == MatchResult::TeamA {
-                stake.team == Team::TeamA
-            } else if m.result == MatchResult::Draw {
-                true // 平局双方均分 / draw -> both share equally
-            } else {
-                stake.team == Team::TeamB
-            };
+        // 查看函数: 比赛事件序号 / View Function: Match Event Sequence
+        // 中文: 返回该比赛最近一次事件的序号（从1开始连续递增，无事件时为0）。索引器发现序号跳跃时应从存储重新同步。
+        // English: Returns the sequence number of the match's latest event (starting at 1 and increasing by one, 0 if
+        //  none). Indexers that see a gap should re-sync from storage.
+        #[ink(message)]
+        pub fn match_event_seq(&self, match_id: u128) -> u64 {
+            self.match_event_seqs.get(&match_id).unwrap_or(0)
+        }
 
-            // 方向总额 / Total stake for user's side
-            let total_stake_team = if stake.team == Team::TeamA { m.total_stake_a } else { m.total_stake_b };
+        // 查看函数: 比赛总数 / View Function: Match Count
+        // 中文: 返回 list_matches 可列出的比赛数（不含已删除与已归档的比赛），前端可据此分页而无需探测 get_match(0..n)。
+        // English: Returns the number of matches list_matches can page through (deleted and archived matches excluded),
+        //  so clients can paginate instead of probing get_match(0..n).
+        #[ink(message)]
+        pub fn match_count(&self) -> u128 {
+            self.index_lens.get(&MatchIndex::All).unwrap_or(0) as u128
+        }
 
-            // 侧向奖池份额 / Side pool share (70/30/50)
-            let pool_share = if m.result == MatchResult::Draw {
-                (m.pool_amount * 50) / 100
-            } else if is_winner {
-                (m.pool_amount * 70) / 100
-            } else {
-                (m.pool_amount * 30) / 100
-            };
+        // 查看函数: 分页列出比赛 / View Function: List Matches
+        // 中文: 分页返回未归档的比赛，limit 最大为 MAX_PAGE_SIZE。通常为创建顺序，归档或删除后顺序可能调整。
+        // English: Returns matches that are not archived, limit capped at MAX_PAGE_SIZE. Usually in creation order, but
+        //  archiving or deleting a match may reorder the list.
+        #[ink(message)]
+        pub fn list_matches(&self, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_page(MatchIndex::All, offset, limit)
+        }
 
-            // 用户按比例分配的奖池 / Proportional pool share for user
-            let user_pool = if total_stake_team == 0 { 0 } else {
-                stake
-                    .amount
-                    .checked_mul(pool_share)
-                    .unwrap_or(0)
-                    / total_stake_team
-            };
+        // 查看函数: 列出已归档比赛 / View Function: List Archived Matches
+        #[ink(message)]
+        pub fn list_archived_matches(&self, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_page(MatchIndex::Archived, offset, limit)
+        }
 
-            // 总到手（未扣费）= 本金 + 奖池分成 / Gross before fee = principal + share
-            let user_share = stake.amount.checked_add(user_pool).unwrap_or(stake.amount);
+        // 查看函数: 按状态列出比赛 / View Function: List Matches by Status
+        // 中文: 状态索引在状态变更时调整，列表顺序不保证与创建顺序一致。已归档的比赛仍会列出（仅 list_matches 隐藏归档）。
+        // English: Status indexes are rebalanced on transitions, so order is not guaranteed to follow creation order.
+        //  Archived matches are still included; only list_matches hides them.
+        #[ink(message)]
+        pub fn list_matches_by_status(&self, status: MatchStatus, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_page(MatchIndex::Status(status), offset, limit)
+        }
 
-            // 是否有手续费接收者（首次注入者） / Whether fee receiver exists
-            let fee_receiver_exists = self.fee_receiver.get(&match_id).is_some();
-            // 手续费5%（若有接收者） / 5% fee if receiver exists
-            let fee = if fee_receiver_exists { user_share / 20 } else { 0 };
-            // 最终可领取金额 / Final payout amount
-            user_share.saturating_sub(fee)
+        // 查看函数: 按管理员列出比赛 / View Function: List Matches by Admin
+        // 中文: 包含已归档的比赛。 / English: Archived matches are included.
+        #[ink(message)]
+        pub fn list_matches_by_admin(&self, admin: AccountId, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_page(MatchIndex::Admin(admin), offset, limit)
         }
 
-        // Add missing function: update_match_teams (only admin, in Pending)
-        // 中文: 仅admin可调用，更新队伍信息，在Pending状态。初学者: 这允许修改队伍标识。
-        // English: Only admin can call, updates team info in Pending status. For beginners: Allows modifying team identifiers.
+        // 查看函数: 按是否内置列出比赛 / View Function: List Matches by Built-in Flag
+        // 中文: 包含已归档的比赛。 / English: Archived matches are included.
         #[ink(message)]
-        pub fn update_match_teams(&mut self, match_id: u128, new_team_a: [u8; 32], new_team_b: [u8; 32]) {
-            let mut match_data = self.matches.get(&match_id).expect("Match not found");
-            assert!(match_data.admin == self.env().caller(), "Only admin");
-            assert!(match_data.status == MatchStatus::Pending, "Can only update in Pending");
-            match_data.team_a = new_team_a;
-            match_data.team_b = new_team_b;
-            self.matches.insert(match_id, &match_data);
-            // Emit event if needed, e.g., TeamUpdated
+        pub fn list_matches_by_built_in(&self, is_built_in: bool, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_page(MatchIndex::BuiltIn(is_built_in), offset, limit)
         }
 
-        // Add new function: emergency_withdraw (only deployer or admin, for all matches)
-        // 中文: 紧急提取合约余额到指定地址，仅部署者调用。初学者: 用于极端情况回收资金。
-        // English: Emergency withdraw contract balance to specified address, only by deployer. For beginners: For extreme cases to recover funds.
+        // 查看函数: 用户持仓 / View Function: User Positions
+        // 中文: 分页返回用户投注过的每场比赛的状态、投注与可领取金额，无需逐个比赛查询。
+        // English: Pages through every match the user has staked in, returning status, stake and pending payout in one call.
+        #[ink(message)]
+        pub fn get_user_positions(&self, user: AccountId, offset: u32, limit: u32) -> Vec<UserPosition> {
+            self.index_ids(MatchIndex::Bettor(user), offset, limit)
+                .into_iter()
+                .filter_map(|match_id| {
+                    let m = self.load_match(match_id)?;
+                    Some(UserPosition {
+                        match_id,
+                        status: m.status,
+                        stakes: self.user_stakes(match_id, user),
+                        pending_payout: self.pending_payout(match_id, user),
+                    })
+                })
+                .collect()
+        }
+
+        // 查看函数: 投注者数量 / View Function: Staker Count
+        #[ink(message)]
+        pub fn staker_count(&self, match_id: u128) -> u32 {
+            self.staker_counts.get(&match_id).unwrap_or(0)
+        }
+
+        // 查看函数: 分页列出投注者 / View Function: List Stakers
+        // 中文: 按投注先后顺序返回 (地址, 投注) 列表，limit 最大为 MAX_PAGE_SIZE（按投注者计，对冲用户会有两项）。
+        //  管理员可据此调用 withdraw_unclaimed 或 distribute。
+        // English: Returns (account, stake) pairs in staking order, limit capped at MAX_PAGE_SIZE (counted in stakers; a
+        //  hedged staker yields two pairs). Lets the admin discover bettors for withdraw_unclaimed or distribute without an external indexer.
+        #[ink(message)]
+        pub fn list_stakers(&self, match_id: u128, offset: u32, limit: u32) -> Vec<(AccountId, Stake)> {
+            let end = self.staker_count(match_id).min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            (offset..end)
+                .filter_map(|index| self.stakers.get(&(match_id, index)))
+                .flat_map(|user| self.user_stakes(match_id, user).into_iter().map(move |stake| (user, stake)))
+                .collect()
+        }
+
+        // 查看函数: 获取投注记录 / View Function: Get Bet
+        #[ink(message)]
+        pub fn get_bet(&self, bet_id: u64) -> Option<Bet> {
+            self.bets.get(&bet_id)
+        }
+
+        // 查看函数: 用户投注次数 / View Function: User Bet Count
+        #[ink(message)]
+        pub fn user_bet_count(&self, match_id: u128, user: AccountId) -> u32 {
+            self.user_bet_counts.get(&(match_id, user)).unwrap_or(0)
+        }
+
+        // 查看函数: 用户投注历史 / View Function: User Bet History
+        // 中文: 按下注顺序分页返回用户在某场比赛的全部投注记录（投注单），limit 最大为 MAX_PAGE_SIZE。
+        // English: Pages through the user's bet slips for a match in placement order, limit capped at MAX_PAGE_SIZE.
+        #[ink(message)]
+        pub fn get_user_bets(&self, match_id: u128, user: AccountId, offset: u32, limit: u32) -> Vec<Bet> {
+            let end = self.user_bet_count(match_id, user).min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            (offset..end)
+                .filter_map(|index| self.user_bets.get(&(match_id, user, index)))
+                .filter_map(|bet_id| self.bets.get(&bet_id))
+                .collect()
+        }
+
+        // 其他函数可根据需要添加 / Additional functions can be added as needed
+
+        // 查看函数: 预览可领取金额 / View Function: Pending Payout Preview
+        // 中文: 返回用户在指定比赛的“可领取最终金额”（扣费后），不改状态、不转账。\n
+        //  - 若比赛未结算/用户无下注/已领取，返回0；对冲用户返回各方向之和。\n
+        //  - 计算与 claim_payout 完全一致：本金 + 按方向分配（赢70%/输30%/平局50%），再扣5%手续费（若有首次注入者）。
+        // English: Returns user's final payable amount for a given match (after fees), read-only.\n
+        //  - Returns 0 if not settled / no stake / already claimed; hedged users get the sum over both sides.\n
+        //  - Same math as claim_payout: principal + side share (win 70% / lose 30% / draw 50%), minus 5% fee if a fee receiver exists.
+        #[ink(message)]
+        pub fn pending_payout(&self, match_id: u128, user: AccountId) -> Balance {
+            // 读取比赛并校验状态 / Load match and ensure it's settled
+            let m = match self.load_match(match_id) {
+                Some(mm) => mm,
+                None => return 0,
+            };
+            if m.status != MatchStatus::Settled { return 0; }
+
+            // 已领取的方向不计入，与 claim_payout 共用同一计算 / Skip claimed positions; same math as claim_payout
+            self.user_stakes(match_id, user)
+                .iter()
+                .filter(|stake| !stake.claimed)
+                // 只读查询不得 panic：溢出时按 0 计并饱和累加 / Views must not panic: overflow counts as 0, saturating sum
+                .map(|stake| self.try_compute_payout(&m, stake).map_or(0, |(_, payout)| payout))
+                .fold(0, Balance::saturating_add)
+        }
+
+        // Add missing function: update_match_teams (only admin, in Pending)
+        // 中文: 仅admin可调用，更新队伍信息，在Pending状态。初学者: 这允许修改队伍标识。
+        // English: Only admin can call, updates team info in Pending status. For beginners: Allows modifying team identifiers.
+        //  中文: 赛事首轮比赛会同步更新对阵表（冠军盘开启后不可再改）；后续轮次的队伍由晋级决定，不可修改。
+        //  English: First-round tournament matches update the bracket too (not once an outright market is open);
+        //  later-round teams come from advancement and cannot be changed.
+        #[ink(message)]
+        pub fn update_match_teams(&mut self, match_id: u128, new_team_a: TeamId, new_team_b: TeamId) {
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.admin == self.env().caller(), "Only admin");
+            assert!(match_data.status == MatchStatus::Pending, "Can only update in Pending");
+            self.ensure_valid_teams(new_team_a, new_team_b);
+            if let Some((tournament_id, slot)) = self.match_slots.get(&match_id) {
+                // 首轮比赛同步对阵表；后续轮次的队伍由晋级决定 / First-round matches keep the bracket in sync;
+                //  later rounds are decided by advancement
+                let tournament = self.tournaments.get(&tournament_id).expect("Tournament not found");
+                assert!(slot < tournament.slot_count.div_ceil(2), "Teams set by bracket");
+                assert!(!self.outright_markets.contains(&tournament_id), "Outright market open");
+                let mut entry = self.bracket_slots.get(&(tournament_id, slot)).expect("Slot not found");
+                entry.team_a = Some(new_team_a);
+                entry.team_b = Some(new_team_b);
+                self.bracket_slots.insert((tournament_id, slot), &entry);
+            }
+            match_data.team_a = new_team_a;
+            match_data.team_b = new_team_b;
+            self.save_match(&match_data);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchTeamsUpdated { match_id, seq, team_a: new_team_a, team_b: new_team_b });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 归档比赛 / Function: Archive Match
+        // 中文: admin或MatchManager可调用，仅限已结算比赛；归档后不出现在 list_matches 与 match_count 中，
+        //  按状态/管理员/内置标志的列表及其余查询、领取不受影响。
+        // English: The admin or a MatchManager can call, for settled matches only. Archived matches drop out of
+        //  list_matches and match_count; the status/admin/built-in listings, other queries and claims keep working.
+        #[ink(message)]
+        pub fn archive_match(&mut self, match_id: u128) {
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            self.ensure_admin_or_role(&match_data, Role::MatchManager);
+            assert!(match_data.status == MatchStatus::Settled, "Match not finished");
+            assert!(!match_data.archived, "Already archived");
+            match_data.archived = true;
+            self.save_match(&match_data);
+            self.index_remove(MatchIndex::All, match_id);
+            self.index_add(MatchIndex::Archived, match_id);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchArchived { match_id, seq });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 删除比赛 / Function: Delete Match
+        // 中文: 仅admin可调用，比赛须非内置、从未有人投注、奖池为空且不在淘汰赛对阵中。清除全部相关存储，
+        //  链上的存储押金随之退还给调用者。
+        // English: Only the admin can call. The match must not be built-in, must never have had a staker, must have an
+        //  empty pool and must not sit in a tournament bracket. All related storage is cleared, which releases the
+        //  storage deposit back to the caller.
+        #[ink(message)]
+        pub fn delete_match(&mut self, match_id: u128) {
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            let caller = self.env().caller();
+            assert!(match_data.admin == caller, "Only admin");
+            assert!(!match_data.is_built_in, "Cannot delete built-in match");
+            assert!(self.staker_count(match_id) == 0 && match_data.pool_amount == 0, "Match has funds");
+            assert!(!self.match_slots.contains(match_id), "Match in tournament");
+
+            self.matches.remove(match_id);
+            self.fee_receiver.remove(match_id);
+            self.stake_limits.remove(match_id);
+            self.pending_match_admins.remove(match_id);
+            self.settlement_summaries.remove(match_id);
+            let listing = if match_data.archived { MatchIndex::Archived } else { MatchIndex::All };
+            self.index_remove(listing, match_id);
+            self.index_remove(MatchIndex::Status(match_data.status), match_id);
+            self.index_remove(MatchIndex::Admin(match_data.admin), match_id);
+            self.index_remove(MatchIndex::BuiltIn(false), match_id);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchDeleted { match_id, seq, by: caller });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 更新比赛元数据 / Function: Update Match Metadata
+        // 中文: 仅admin可调用，且只能在Pending状态，整体替换元数据。
+        // English: Only the admin can call, and only while Pending; replaces the metadata as a whole.
+        #[ink(message)]
+        pub fn update_match_metadata(&mut self, match_id: u128, metadata: MatchMetadata) {
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.admin == self.env().caller(), "Only admin");
+            assert!(match_data.status == MatchStatus::Pending, "Can only update in Pending");
+            Self::ensure_valid_metadata(&metadata);
+            match_data.metadata = metadata.clone();
+            self.save_match(&match_data);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchMetadataUpdated { match_id, seq, metadata });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 全局暂停 / Function: Pause
+        // 中文: Pauser可调用，暂停后所有改变状态的操作（投注、领取、结算、配置等）都会被拒绝；
+        //  例外仅有暂停开关、角色与所有权管理、升级与迁移以及紧急提取，见 ensure_not_paused。
+        // English: Callable by a Pauser. While paused every state-changing operation (staking, claiming, settling,
+        //  configuration, ...) is rejected; the only exceptions are the pause switches, role and ownership
+        //  administration, upgrades and migration, and emergency withdrawal (see ensure_not_paused).
+        #[ink(message)]
+        pub fn pause(&mut self) {
+            self.ensure_role(Role::Pauser);
+            assert!(!self.paused, "Already paused");
+            self.paused = true;
+            self.env().emit_event(Paused { match_id: None, seq: None, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 全局恢复 / Function: Unpause
+        #[ink(message)]
+        pub fn unpause(&mut self) {
+            self.ensure_role(Role::Pauser);
+            assert!(self.paused, "Not paused");
+            self.paused = false;
+            self.env().emit_event(Unpaused { match_id: None, seq: None, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 暂停单场比赛 / Function: Pause Match
+        // 中文: 只冻结指定比赛，其他比赛照常运行。
+        // English: Freezes only the given match; other matches keep running.
+        #[ink(message)]
+        pub fn pause_match(&mut self, match_id: u128) {
+            self.set_match_paused(match_id, true);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(Paused { match_id: Some(match_id), seq: Some(seq), by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 恢复单场比赛 / Function: Unpause Match
+        #[ink(message)]
+        pub fn unpause_match(&mut self, match_id: u128) {
+            self.set_match_paused(match_id, false);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(Unpaused { match_id: Some(match_id), seq: Some(seq), by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 授予角色 / Function: Grant Role
+        // 中文: 仅Owner可调用。
+        // English: Only an Owner can call.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) {
+            self.ensure_role(Role::Owner);
+            assert!(!self.has_role(role, account), "Role already granted");
+            self.roles.insert((role, account), &());
+            self.env().emit_event(RoleGranted { role, account, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 撤销角色 / Function: Revoke Role
+        // 中文: 仅Owner可调用；主所有者的Owner角色不可撤销（需通过 transfer_ownership 转移），避免合约失去管理者。
+        // English: Only an Owner can call; the primary owner's Owner role cannot be revoked so the contract is never left ownerless.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) {
+            self.ensure_role(Role::Owner);
+            assert!(!(role == Role::Owner && account == self.owner), "Cannot revoke primary owner");
+            assert!(self.has_role(role, account), "Role not granted");
+            self.roles.remove(&(role, account));
+            self.env().emit_event(RoleRevoked { role, account, by: self.env().caller() });
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 是否拥有角色 / View Function: Has Role
+        // 中文: 只反映显式授予的角色，不含Owner的隐含权限。
+        // English: Reflects explicitly granted roles only, not the Owner's implied permissions.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.contains(&(role, account))
+        }
+
+        // 查看函数: 是否全局暂停 / View Function: Is Paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        // 函数: 发起所有权转移 / Function: Transfer Ownership
+        // 中文: 仅主所有者可调用，第一步：记录待接受的新所有者，需由对方调用 accept_ownership 完成，防止转给错误地址。
+        //  再次调用会覆盖之前的待接受地址。
+        // English: Only the primary owner can call. Step one records the pending owner, who must call accept_ownership,
+        //  so a mistyped address cannot strand the contract. Calling again replaces the pending owner.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) {
+            assert!(self.owner == self.env().caller(), "Only owner");
+            self.pending_owner = Some(new_owner);
+            self.env().emit_event(OwnershipTransferStarted {
+                previous_owner: self.owner,
+                pending_owner: new_owner,
+            });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 接受所有权 / Function: Accept Ownership
+        // 中文: 第二步：待接受的新所有者调用，Owner角色从旧所有者转移到新所有者；旧所有者担任的 system_admin
+        //  及其管理的内置比赛也转给新所有者。
+        // English: Step two, called by the pending owner; the Owner role moves from the previous owner to the new one,
+        //  together with the system_admin seat and any built-in matches the previous owner held.
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) {
+            let caller = self.env().caller();
+            assert!(self.pending_owner == Some(caller), "Not pending owner");
+            let previous_owner = self.owner;
+            self.pending_owner = None;
+            self.owner = caller;
+            if self.roles.take(&(Role::Owner, previous_owner)).is_some() {
+                self.env().emit_event(RoleRevoked { role: Role::Owner, account: previous_owner, by: caller });
+            }
+            if !self.has_role(Role::Owner, caller) {
+                self.roles.insert((Role::Owner, caller), &());
+                self.env().emit_event(RoleGranted { role: Role::Owner, account: caller, by: caller });
+            }
+            // 旧所有者担任的 system_admin 与其管理的内置比赛随所有权一并转移
+            // The system_admin seat and built-ins held by the previous owner move with ownership
+            if self.system_admin() == previous_owner {
+                self.replace_system_admin(caller);
+            }
+            self.reassign_built_in_matches(previous_owner, caller);
+            self.env().emit_event(OwnershipTransferred { previous_owner, new_owner: caller });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 取消所有权转移 / Function: Cancel Ownership Transfer
+        // 中文: 仅主所有者可调用，撤销尚未被接受的所有权转移。
+        // English: Only the primary owner can call; withdraws an ownership transfer that has not been accepted yet.
+        #[ink(message)]
+        pub fn cancel_ownership_transfer(&mut self) {
+            assert!(self.owner == self.env().caller(), "Only owner");
+            let pending_owner = self.pending_owner.take().expect("No pending owner");
+            self.env().emit_event(OwnershipTransferCancelled { owner: self.owner, pending_owner });
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 主所有者 / View Function: Owner
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        // 查看函数: 待接受的所有者 / View Function: Pending Owner
+        #[ink(message)]
+        pub fn pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner
+        }
+
+        // 函数: 发起比赛admin转移 / Function: Transfer Match Admin
+        // 中文: 仅当前admin可调用，第一步：记录待接受的新admin，需由对方调用 accept_match_admin 完成。
+        // English: Only the current admin can call. Step one records the pending admin, who must call accept_match_admin.
+        #[ink(message)]
+        pub fn transfer_match_admin(&mut self, match_id: u128, new_admin: AccountId) {
+            let match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            assert!(match_data.admin == self.env().caller(), "Only admin");
+            self.pending_match_admins.insert(match_id, &new_admin);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchAdminTransferStarted {
+                match_id,
+                seq,
+                previous_admin: match_data.admin,
+                pending_admin: new_admin,
+            });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 接受比赛admin / Function: Accept Match Admin
+        // 中文: 第二步：待接受的新admin调用，比赛admin与管理员索引随之更新。
+        // English: Step two, called by the pending admin; the match admin and the admin index are updated.
+        #[ink(message)]
+        pub fn accept_match_admin(&mut self, match_id: u128) {
+            let caller = self.env().caller();
+            assert!(self.pending_match_admins.get(&match_id) == Some(caller), "Not pending admin");
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            self.ensure_match_active(&match_data);
+            let previous_admin = match_data.admin;
+            self.pending_match_admins.remove(&match_id);
+            self.index_remove(MatchIndex::Admin(previous_admin), match_id);
+            self.index_add(MatchIndex::Admin(caller), match_id);
+            match_data.admin = caller;
+            self.save_match(&match_data);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchAdminTransferred {
+                match_id,
+                seq,
+                previous_admin,
+                new_admin: caller,
+            });
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 待接受的比赛admin / View Function: Pending Match Admin
+        #[ink(message)]
+        pub fn pending_match_admin(&self, match_id: u128) -> Option<AccountId> {
+            self.pending_match_admins.get(&match_id)
+        }
+
+        // 函数: 提议升级 / Function: Propose Upgrade
+        // 中文: 仅Owner可调用，登记新的代码哈希并开始计时，UPGRADE_TIMELOCK_MS 之后才能执行 upgrade，给用户留出退出时间。
+        // English: Only an Owner can call; registers the new code hash and starts the timelock. upgrade can only run
+        //  UPGRADE_TIMELOCK_MS later, giving users time to exit.
+        #[ink(message)]
+        pub fn propose_upgrade(&mut self, code_hash: Hash) {
+            self.ensure_role(Role::Owner);
+            let eta = self.env().block_timestamp().saturating_add(UPGRADE_TIMELOCK_MS);
+            self.pending_upgrade = Some(PendingUpgrade { code_hash, eta });
+            self.env().emit_event(UpgradeProposed { code_hash, eta });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 取消升级 / Function: Cancel Upgrade
+        #[ink(message)]
+        pub fn cancel_upgrade(&mut self) {
+            self.ensure_role(Role::Owner);
+            let pending = self.pending_upgrade.take().expect("No pending upgrade");
+            self.env().emit_event(UpgradeCancelled { code_hash: pending.code_hash });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 执行升级 / Function: Upgrade
+        // 中文: 仅Owner可调用，code_hash 须与提案一致且时间锁已到期，通过 set_code_hash 替换合约代码，存储保持不变。
+        //  若新代码的 STORAGE_VERSION 更高，需随后调用 migrate 把旧记录写回新布局。
+        // English: Only an Owner can call. The code hash must match the proposal and the timelock must have expired; the
+        //  code is swapped via set_code_hash while storage stays in place. If the new code has a higher STORAGE_VERSION,
+        //  call migrate afterwards to rewrite old records.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) {
+            self.ensure_role(Role::Owner);
+            let pending = self.pending_upgrade.clone().expect("No pending upgrade");
+            assert!(pending.code_hash == code_hash, "Code hash mismatch");
+            assert!(self.env().block_timestamp() >= pending.eta, "Upgrade timelocked");
+            self.pending_upgrade = None;
+            let raw: &[u8; 32] = code_hash.as_ref().try_into().expect("Invalid code hash");
+            ink::env::set_code_hash(raw).expect("Failed to set code hash");
+            self.env().emit_event(Upgraded { code_hash });
+            self.debug_assert_solvent();
+        }
+
+        // 函数: 迁移存储 / Function: Migrate
+        // 中文: 仅Owner可调用，每次最多检查 limit 场比赛，把旧版本记录写回最新布局；全部完成后更新 storage_version。
+        //  返回是否已完成。第一步先补齐内置队伍注册表。迁移完成前读取仍会即时转换，但其余修改状态的消息一律拒绝。
+        // English: Only an Owner can call. Checks up to `limit` matches per call and rewrites old records in the latest
+        //  layout; storage_version is bumped once everything is done. Returns whether migration is complete. The first
+        //  step seeds the built-in team registry. Until migration completes, reads convert on the fly but every other
+        //  state-changing message is rejected.
+        #[ink(message)]
+        pub fn migrate(&mut self, limit: u32) -> bool {
+            self.ensure_role(Role::Owner);
+            let done = self.migrate_batch(limit);
+            self.debug_assert_solvent();
+            done
+        }
+
+        // 内部函数: 迁移一批比赛 / Internal: Migrate Batch
+        fn migrate_batch(&mut self, limit: u32) -> bool {
+            if self.storage_version == STORAGE_VERSION {
+                return true;
+            }
+            // v1 没有队伍注册表，先补齐内置队伍 / v1 had no team registry; seed the built-in teams first
+            if self.next_team_id.get().is_none() {
+                let caller = self.env().caller();
+                self.register_built_in_teams(caller);
+            }
+            let end = self.next_match_id.min(self.migration_cursor.saturating_add(limit as u128));
+            for match_id in self.migration_cursor..end {
+                if let Some(record) = self.matches.get(&match_id) {
+                    if !record.is_latest() {
+                        self.save_match(&record.into_latest());
+                    }
+                }
+            }
+            self.migration_cursor = end;
+            if end < self.next_match_id {
+                return false;
+            }
+            self.env().emit_event(Migrated {
+                from_version: self.storage_version,
+                to_version: STORAGE_VERSION,
+            });
+            self.storage_version = STORAGE_VERSION;
+            self.migration_cursor = 0;
+            true
+        }
+
+        // 查看函数: 存储版本 / View Function: Storage Version
+        #[ink(message)]
+        pub fn storage_version(&self) -> u32 {
+            self.storage_version
+        }
+
+        // 查看函数: 待执行的升级 / View Function: Pending Upgrade
+        #[ink(message)]
+        pub fn pending_upgrade(&self) -> Option<PendingUpgrade> {
+            self.pending_upgrade.clone()
+        }
+
+        // Add new function: emergency_withdraw (Treasurer only, for all matches)
+        // 中文: 紧急提取合约盈余到指定地址，仅Treasurer调用。只能提取 余额 - 负债（投注、奖池、未领取奖金），用户资金不可动用。
+        // English: Emergency withdraw of surplus funds to a specified address, only by a Treasurer. Limited to balance minus
+        //  liabilities (stakes, pools, unclaimed payouts), so user funds can never be moved.
         #[ink(message)]
         pub fn emergency_withdraw(&mut self, to: AccountId, amount: Balance) {
-            // 只允许合约部署者 / Only contract deployer
-            // 假设添加一个deployer存储 / Assume adding a deployer in storage
-            // 先在storage添加: deployer: AccountId,
-            // 在new(): instance.deployer = Self::env().caller();
+            self.ensure_role(Role::Treasurer);
+            let surplus = self.env().balance().saturating_sub(self.total_liabilities);
+            assert!(amount <= surplus, "Exceeds surplus");
+            self.env().transfer(to, amount).expect("Transfer failed");
+            self.env().emit_event(EmergencyWithdrawal {
+                to,
+                amount,
+                by: self.env().caller(),
+            });
+            self.debug_assert_solvent();
+        }
+
+        // 查看函数: 未偿负债 / View Function: Total Liabilities
+        // 中文: 负债计数器由 注入/投注/撤注/结算/领取 各路径增量维护。
+        // English: The liability counter is maintained incrementally by the inject/stake/withdraw/settle/claim paths.
+        #[ink(message)]
+        pub fn total_liabilities(&self) -> Balance {
+            self.total_liabilities
+        }
+
+        // 查看函数: 偿付能力审计 / View Function: Audit Solvency
+        // 中文: 只读，返回合约余额、负债与盈余，链外可随时核对合约是否足额覆盖所有比赛与投注的应付款。
+        // English: Read-only; returns balance, liabilities and surplus so anyone can verify the contract covers what it owes
+        //  across all matches and stakes.
+        #[ink(message)]
+        pub fn audit_solvency(&self) -> SolvencyReport {
+            let balance = self.env().balance();
+            SolvencyReport {
+                balance,
+                liabilities: self.total_liabilities,
+                surplus: balance.saturating_sub(self.total_liabilities),
+                solvent: balance >= self.total_liabilities,
+            }
+        }
+
+        // 内部函数: 读取比赛 / Internal: Load Match
+        // 中文: 读取任意版本的比赛记录并转换为最新布局。
+        // English: Loads a match record of any version and converts it to the latest layout.
+        fn load_match(&self, match_id: u128) -> Option<Match> {
+            self.matches.get(&match_id).map(VersionedMatch::into_latest)
+        }
+
+        // 内部函数: 写入比赛 / Internal: Save Match
+        // 中文: 总是以最新布局写入。
+        // English: Always writes the latest layout.
+        fn save_match(&mut self, match_data: &Match) {
+            self.matches.insert(match_data.id, &VersionedMatch::V4(match_data.clone()));
+        }
+
+        // 内部函数: 设置单场暂停 / Internal: Set Match Paused
+        fn set_match_paused(&mut self, match_id: u128, paused: bool) {
+            self.ensure_role(Role::Pauser);
+            let mut match_data = self.load_match(match_id).expect("Match not found");
+            assert!(match_data.paused != paused, "Pause state unchanged");
+            match_data.paused = paused;
+            self.save_match(&match_data);
+        }
+
+        // 内部函数: 写入新赛事 / Internal: Insert New Tournament
+        // 中文: 建立全部对阵位，首轮对阵位关联已有比赛。构造函数与 create_tournament 共用。
+        // English: Lays out every slot and links the first-round slots to existing matches. Shared by the constructor
+        //  and create_tournament.
+        fn insert_tournament(&mut self, admin: AccountId, name: String, first_round: Vec<u128>) -> TournamentId {
+            let tournament_id = self.next_tournament_id.get().unwrap_or(0);
+            self.next_tournament_id.set(&(tournament_id + 1));
+            let first = first_round.len() as u32;
+            let (mut slot, mut size, mut round) = (0u32, first, 0u8);
+            while size > 0 {
+                for _ in 0..size {
+                    let entry = match first_round.get(slot as usize) {
+                        Some(match_id) if round == 0 => {
+                            let match_data = self.load_match(*match_id).expect("Match not found");
+                            self.match_slots.insert(match_id, &(tournament_id, slot));
+                            BracketSlot {
+                                slot,
+                                round,
+                                match_id: Some(*match_id),
+                                team_a: Some(match_data.team_a),
+                                team_b: Some(match_data.team_b),
+                                winner: None,
+                            }
+                        }
+                        _ => BracketSlot { slot, round, match_id: None, team_a: None, team_b: None, winner: None },
+                    };
+                    self.bracket_slots.insert((tournament_id, slot), &entry);
+                    slot += 1;
+                }
+                size /= 2;
+                round += 1;
+            }
+            self.tournaments.insert(
+                tournament_id,
+                &Tournament { id: tournament_id, name, admin, rounds: round, slot_count: slot, champion: None },
+            );
+            self.env().emit_event(TournamentCreated { tournament_id, admin, first_round });
+            tournament_id
+        }
+
+        // 内部函数: 记录对阵位胜者 / Internal: Record Slot Winner
+        // 中文: 写入胜者并填入下一轮对阵位；下一轮双方都确定后自动创建比赛（admin为赛事admin）。决赛胜者即冠军。
+        // English: Stores the winner and fills the next-round slot; once both sides are known the fixture is created
+        //  with the tournament admin as its admin. The winner of the final becomes champion.
+        fn record_slot_winner(&mut self, tournament_id: TournamentId, slot: u32, winner: TeamId) {
+            let mut tournament = self.tournaments.get(&tournament_id).expect("Tournament not found");
+            let mut entry = self.bracket_slots.get(&(tournament_id, slot)).expect("Slot not found");
+            entry.winner = Some(winner);
+            self.bracket_slots.insert((tournament_id, slot), &entry);
+
+            let first_round = tournament.slot_count.div_ceil(2);
+            let mut next_match_id = None;
+            if slot + 1 == tournament.slot_count {
+                tournament.champion = Some(winner);
+                self.tournaments.insert(tournament_id, &tournament);
+                self.env().emit_event(TournamentCompleted { tournament_id, champion: winner });
+                if let Some(market) = self.outright_markets.get(&tournament_id) {
+                    if market.winner.is_none() {
+                        self.finalize_outright(market, winner);
+                    }
+                }
+            } else {
+                let next_slot = first_round + slot / 2;
+                let mut next = self.bracket_slots.get(&(tournament_id, next_slot)).expect("Slot not found");
+                if slot % 2 == 0 {
+                    next.team_a = Some(winner);
+                } else {
+                    next.team_b = Some(winner);
+                }
+                if let (Some(team_a), Some(team_b)) = (next.team_a, next.team_b) {
+                    // 所属赛事通过 match_bracket_slot 查询 / The owning tournament is found via match_bracket_slot
+                    let match_id =
+                        self.insert_new_match(tournament.admin, team_a, team_b, false, MatchMetadata::default());
+                    self.match_slots.insert(match_id, &(tournament_id, next_slot));
+                    next.match_id = Some(match_id);
+                    next_match_id = Some(match_id);
+                }
+                self.bracket_slots.insert((tournament_id, next_slot), &next);
+            }
+            self.env().emit_event(BracketAdvanced { tournament_id, slot, winner, next_match_id });
+        }
+
+        // 内部函数: 对阵表是否卡住 / Internal: Is Bracket Stuck
+        // 中文: 存在尚无胜者的位置，且其比赛已不存在，或以平局结算后超过 DRAW_RESOLUTION_WINDOW_MS 仍未指定晋级者。
+        //  未开赛或进行中的比赛不算卡住。
+        // English: Some slot has no winner yet and its match no longer exists, or was settled as a draw more than
+        //  DRAW_RESOLUTION_WINDOW_MS ago without a winner being advanced. Unplayed or ongoing matches do not count.
+        fn is_bracket_stuck(&self, tournament: &Tournament) -> bool {
+            let now = self.env().block_timestamp();
+            (0..tournament.slot_count)
+                .filter_map(|slot| self.bracket_slots.get(&(tournament.id, slot)))
+                .filter(|entry| entry.winner.is_none())
+                .filter_map(|entry| entry.match_id)
+                .any(|match_id| match self.load_match(match_id) {
+                    None => true,
+                    Some(m) if m.status == MatchStatus::Settled && m.result == MatchResult::Draw => {
+                        // 无汇总的旧记录视为早已结算 / Legacy records without a summary count as long settled
+                        let settled_at = self.settlement_summaries.get(&match_id).map_or(0, |s| s.settled_at);
+                        now >= settled_at.saturating_add(DRAW_RESOLUTION_WINDOW_MS)
+                    }
+                    Some(_) => false,
+                })
+        }
+
+        // 内部函数: 是否参赛队伍 / Internal: Is Tournament Team
+        fn is_tournament_team(&self, tournament_id: TournamentId, team: TeamId) -> bool {
+            let Some(tournament) = self.tournaments.get(&tournament_id) else {
+                return false;
+            };
+            (0..tournament.slot_count.div_ceil(2))
+                .filter_map(|slot| self.bracket_slots.get(&(tournament_id, slot)))
+                .any(|entry| entry.team_a == Some(team) || entry.team_b == Some(team))
+        }
+
+        // 内部函数: 结算冠军盘 / Internal: Finalize Outright Market
+        // 中文: 写入冠军；无人投注的一方（冠军方70% / 其余30%）对应的奖池份额无人可领，不再计为负债。
+        // English: Stores the champion; the pool share of a side nobody backed (champion 70% / rest 30%) can never be
+        //  claimed and stops counting as a liability.
+        fn finalize_outright(&mut self, mut market: OutrightMarket, winner: TeamId) {
+            market.winner = Some(winner);
+            self.outright_markets.insert(market.tournament_id, &market);
+            let winning = self.outright_team_total(market.tournament_id, winner);
+            let mut unowed: Balance = 0;
+            if winning == 0 {
+                unowed += (market.pool_amount * 70) / 100;
+            }
+            if market.total_staked == winning {
+                unowed += (market.pool_amount * 30) / 100;
+            }
+            self.total_liabilities = self.total_liabilities.saturating_sub(unowed);
+            self.env().emit_event(OutrightSettled { tournament_id: market.tournament_id, winner });
+        }
+
+        // 内部函数: 计算冠军盘奖金 / Internal: Compute Outright Payout
+        // 中文: 返回 (手续费, 实付)，规则见 OutrightMarket。
+        // English: Returns (fee, payout); see OutrightMarket for the rules.
+        fn compute_outright_payout(&self, market: &OutrightMarket, stake: &OutrightStake) -> (Balance, Balance) {
+            let winning = self.outright_team_total(market.tournament_id, stake.team);
+            let (pool_share, side_total) = if market.winner == Some(stake.team) {
+                ((market.pool_amount * 70) / 100, winning)
+            } else {
+                let champion_total = market.winner.map_or(0, |w| self.outright_team_total(market.tournament_id, w));
+                ((market.pool_amount * 30) / 100, market.total_staked - champion_total)
+            };
+            let user_pool = stake
+                .amount
+                .checked_mul(pool_share)
+                .expect("Overflow")
+                .checked_div(side_total)
+                .unwrap_or(0);
+            let user_share = stake.amount.checked_add(user_pool).expect("Overflow");
+            if market.pool_injected_by.is_some() {
+                let fee = user_share.checked_mul(FEE_BPS as Balance).expect("Overflow") / BPS_DENOMINATOR as Balance;
+                (fee, user_share - fee)
+            } else {
+                (0, user_share)
+            }
+        }
+
+        // 内部函数: 写入新队伍 / Internal: Insert New Team
+        fn insert_team(&mut self, by: AccountId, name: String, short_code: String, country_code: String, logo_cid: String) -> TeamId {
+            let team_id = self.team_count();
+            self.next_team_id.set(&(team_id + 1));
+            self.team_by_code.insert(&short_code, &team_id);
+            self.teams.insert(team_id, &TeamInfo { id: team_id, name, short_code: short_code.clone(), country_code, logo_cid });
+            self.env().emit_event(TeamRegistered { team_id, short_code, by });
+            team_id
+        }
+
+        // 内部函数: 注册内置队伍 / Internal: Register Built-in Teams
+        fn register_built_in_teams(&mut self, by: AccountId) {
+            for (name, short_code, country_code) in BUILT_IN_TEAMS {
+                self.insert_team(by, name.into(), short_code.into(), country_code.into(), String::new());
+            }
+        }
+
+        // 内部函数: 校验比赛双方 / Internal: Validate Match Teams
+        fn ensure_valid_teams(&self, team_a: TeamId, team_b: TeamId) {
+            assert!(self.teams.contains(team_a) && self.teams.contains(team_b), "Team not found");
+            assert!(team_a != team_b, "Teams must differ");
+        }
+
+        // 内部函数: 校验比赛元数据 / Internal: Validate Match Metadata
+        fn ensure_valid_metadata(metadata: &MatchMetadata) {
+            assert!(
+                [&metadata.stage, &metadata.venue, &metadata.metadata_cid]
+                    .iter()
+                    .all(|f| f.as_ref().map_or(0, String::len) <= MAX_METADATA_FIELD_LEN),
+                "Metadata field too long"
+            );
+        }
+
+        // 内部函数: 下一个事件序号 / Internal: Next Event Sequence
+        // 中文: 每场比赛的事件都携带该序号；比赛删除后仍保留计数，避免重复序号。
+        // English: Every match event carries this number; the counter survives deletion so numbers are never reused.
+        fn next_event_seq(&mut self, match_id: u128) -> u64 {
+            let seq = self.match_event_seq(match_id) + 1;
+            self.match_event_seqs.insert(match_id, &seq);
+            seq
+        }
+
+        // 内部函数: 写入内置比赛 / Internal: Insert Built-in Match
+        fn insert_built_in_match(&mut self, team_a: TeamId, team_b: TeamId, metadata: MatchMetadata) -> u128 {
+            self.ensure_valid_teams(team_a, team_b);
+            Self::ensure_valid_metadata(&metadata);
+            let admin = self.system_admin();
+            self.insert_new_match(admin, team_a, team_b, true, metadata)
+        }
+
+        // 内部函数: 更换内置比赛admin / Internal: Replace System Admin
+        fn replace_system_admin(&mut self, new_admin: AccountId) {
+            let previous_admin = self.system_admin();
+            self.system_admin.set(&new_admin);
+            self.reassign_built_in_matches(previous_admin, new_admin);
+            self.env().emit_event(SystemAdminUpdated { previous_admin, new_admin });
+        }
+
+        // 内部函数: 转移内置比赛 / Internal: Reassign Built-in Matches
+        // 中文: 把 admin 为 from 的内置比赛转给 to，同步管理员索引并逐场发出 MatchAdminTransferred。
+        // English: Hands every built-in match administered by `from` to `to`, updating the admin index and emitting
+        //  MatchAdminTransferred for each match.
+        fn reassign_built_in_matches(&mut self, from: AccountId, to: AccountId) {
+            if from == to {
+                return;
+            }
+            let index = MatchIndex::BuiltIn(true);
+            let len = self.index_lens.get(&index).unwrap_or(0);
+            for position in 0..len {
+                let Some(match_id) = self.index_entries.get(&(index, position)) else { continue };
+                let Some(mut match_data) = self.load_match(match_id) else { continue };
+                if match_data.admin != from {
+                    continue;
+                }
+                self.index_remove(MatchIndex::Admin(from), match_id);
+                self.index_add(MatchIndex::Admin(to), match_id);
+                match_data.admin = to;
+                self.save_match(&match_data);
+                let seq = self.next_event_seq(match_id);
+                self.env().emit_event(MatchAdminTransferred {
+                    match_id,
+                    seq,
+                    previous_admin: from,
+                    new_admin: to,
+                });
+            }
+        }
+
+        // 内部函数: 写入新比赛 / Internal: Insert New Match
+        // 中文: 分配ID、写入存储、登记二级索引并发出 MatchCreated 事件。构造函数与 create_match 共用。
+        // English: Allocates an id, stores the match, registers it in the secondary indexes and emits MatchCreated.
+        //  Shared by the constructor and create_match.
+        fn insert_new_match(
+            &mut self,
+            admin: AccountId,
+            team_a: TeamId,
+            team_b: TeamId,
+            is_built_in: bool,
+            metadata: MatchMetadata,
+        ) -> u128 {
+            let match_id = self.next_match_id;
+            self.next_match_id += 1;
+            self.save_match(&Match {
+                id: match_id,
+                admin,
+                team_a,
+                team_b,
+                is_built_in,
+                pool_injected_by: None,
+                pool_amount: 0,
+                status: MatchStatus::Pending,
+                result: MatchResult::None,
+                total_stake_a: 0,
+                total_stake_b: 0,
+                paused: false,
+                metadata,
+                archived: false,
+            });
+            self.index_add(MatchIndex::All, match_id);
+            self.index_add(MatchIndex::Status(MatchStatus::Pending), match_id);
+            self.index_add(MatchIndex::Admin(admin), match_id);
+            self.index_add(MatchIndex::BuiltIn(is_built_in), match_id);
+            let seq = self.next_event_seq(match_id);
+            self.env().emit_event(MatchCreated {
+                match_id,
+                seq,
+                admin,
+                team_a,
+                team_b,
+                is_built_in,
+            });
+            match_id
+        }
+
+        // 内部函数: 调试断言偿付能力 / Internal: Debug-Assert Solvency
+        // 中文: 仅在调试构建（含单元测试）中生效，在每个 &mut self 消息结束时检查 余额 >= 负债，
+        //  不区分是否移动资金，以免之后的改动在未覆盖的路径上破坏该不变量。
+        // English: Active in debug builds only (unit tests included); checks balance >= liabilities at the end of every
+        //  `&mut self` message, whether or not it moves funds, so later changes cannot break the invariant on an
+        //  unchecked path.
+        fn debug_assert_solvent(&self) {
+            debug_assert!(self.env().balance() >= self.total_liabilities, "Solvency invariant violated");
+        }
+
+        // 内部函数: 校验未暂停 / Internal: Ensure Not Paused
+        // 中文: 除以下消息外，所有修改状态的消息都须经过本校验（或 ensure_match_active）：
+        //  - pause/unpause/pause_match/unpause_match：暂停开关本身；
+        //  - grant_role/revoke_role、transfer_ownership/accept_ownership、set_system_admin 等权限管理：事故中须能撤销泄露的角色或移交控制权；
+        //  - propose_upgrade/cancel_upgrade/upgrade/migrate：通常在暂停期间执行修复升级与迁移；
+        //  - emergency_withdraw：只能提取盈余，不触及用户资金。
+        //  迁移未完成时同样拒绝这些消息（旧布局下的队伍注册表等尚未补齐），只读查询不受影响。
+        // English: Every state-changing message goes through this check (or ensure_match_active), except:
+        //  - pause/unpause/pause_match/unpause_match: the switches themselves;
+        //  - role and ownership administration (grant_role/revoke_role, transfer_ownership/accept_ownership,
+        //    set_system_admin, ...):
+        //    a leaked role must be revocable and control transferable during an incident;
+        //  - propose_upgrade/cancel_upgrade/upgrade/migrate: fix-up upgrades and migrations usually run while paused;
+        //  - emergency_withdraw: limited to the surplus, so user funds are never touched.
+        //  While a migration is pending the same messages are rejected as well (e.g. the team registry may not be
+        //  seeded yet); read-only queries keep working.
+        fn ensure_not_paused(&self) {
+            assert!(!self.paused, "Contract paused");
+            assert!(self.storage_version == STORAGE_VERSION, "Migration pending");
+        }
+
+        // 内部函数: 校验比赛可操作 / Internal: Ensure Match Active
+        // 中文: 全局或本场暂停时拒绝所有改变该比赛状态的操作。
+        // English: Rejects any state change on the match while the contract or the match itself is paused.
+        fn ensure_match_active(&self, match_data: &Match) {
+            self.ensure_not_paused();
+            assert!(!match_data.paused, "Match paused");
+        }
+
+        // 内部函数: 校验角色 / Internal: Ensure Role
+        // 中文: 调用者须拥有该角色或Owner角色。
+        // English: The caller must hold the role, or the Owner role.
+        fn ensure_role(&self, role: Role) {
+            let caller = self.env().caller();
+            assert!(self.has_role(role, caller) || self.has_role(Role::Owner, caller), "Missing role");
+        }
+
+        // 内部函数: 校验比赛admin或角色 / Internal: Ensure Match Admin or Role
+        fn ensure_admin_or_role(&self, match_data: &Match, role: Role) {
+            let caller = self.env().caller();
+            assert!(
+                match_data.admin == caller || self.has_role(role, caller) || self.has_role(Role::Owner, caller),
+                "Only admin"
+            );
+        }
+
+        // 内部函数: 校验赛事admin或角色 / Internal: Ensure Tournament Admin or Role
+        fn ensure_tournament_admin_or_role(&self, tournament: &Tournament, role: Role) {
+            let caller = self.env().caller();
+            assert!(
+                tournament.admin == caller || self.has_role(role, caller) || self.has_role(Role::Owner, caller),
+                "Only admin"
+            );
+        }
+
+        // 内部函数: 校验投注限额 / Internal: Check Stake Limits
+        // 中文: 依次校验单笔最小额、单用户上限、本场总额上限与账户敞口上限，各自给出不同的错误信息。
+        // English: Checks the minimum stake, per-user cap, match total cap and account exposure cap, each with its own error.
+        fn check_stake_limits(&self, match_data: &Match, user: AccountId, amount: Balance) {
+            let limits = self.get_stake_limits(match_data.id);
+            assert!(amount >= limits.min_stake, "Below minimum stake");
+            if let Some(max) = limits.max_stake_per_user {
+                let current = self
+                    .user_stakes(match_data.id, user)
+                    .iter()
+                    .fold(0, |total: Balance, stake| total.saturating_add(stake.amount));
+                assert!(current.saturating_add(amount) <= max, "Exceeds max stake per user");
+            }
+            if let Some(max) = limits.max_total_pool {
+                let total = match_data.total_stake_a.saturating_add(match_data.total_stake_b);
+                assert!(total.saturating_add(amount) <= max, "Exceeds max total pool");
+            }
+            if let Some(cap) = self.max_account_exposure {
+                assert!(self.account_exposure(user).saturating_add(amount) <= cap, "Exceeds account exposure cap");
+            }
+        }
+
+        // 内部函数: 登记敞口比赛 / Internal: Track Exposure
+        // 中文: 剔除账户列表中已非Open（或已删除）的比赛，再把本场加入列表（若尚未存在）。
+        // English: Drops matches that are no longer open (or were deleted) from the account's list, then adds this
+        //  match if it is not listed yet.
+        fn track_exposure(&mut self, user: AccountId, match_id: u128) {
+            let len = self.exposure_match_counts.get(&user).unwrap_or(0);
+            let mut kept = 0u32;
+            let mut listed = false;
+            for index in 0..len {
+                let Some(id) = self.exposure_matches.take(&(user, index)) else { continue };
+                if !self.load_match(id).is_some_and(|m| m.status == MatchStatus::Open) {
+                    continue;
+                }
+                listed |= id == match_id;
+                self.exposure_matches.insert((user, kept), &id);
+                kept += 1;
+            }
+            if !listed {
+                self.exposure_matches.insert((user, kept), &match_id);
+                kept += 1;
+            }
+            self.exposure_match_counts.insert(user, &kept);
+        }
+
+        // 内部函数: 记录单笔投注 / Internal: Record Bet
+        // 中文: match_data 须已包含本笔投注，隐含赔率据此计算。
+        // English: `match_data` must already include this bet; the implied odds are derived from it.
+        fn record_bet(&mut self, match_data: &Match, user: AccountId, team: Team, amount: Balance) -> u64 {
+            let side_total = if team == Team::TeamA {
+                match_data.total_stake_a
+            } else {
+                match_data.total_stake_b
+            };
+            let win_share = (match_data.pool_amount * 70) / 100;
+            let implied_odds = side_total
+                .checked_add(win_share)
+                .and_then(|gross| gross.checked_mul(ODDS_PRECISION))
+                .expect("Overflow")
+                / side_total;
+
+            let bet_id = self.next_bet_id;
+            self.next_bet_id += 1;
+            self.bets.insert(bet_id, &Bet {
+                id: bet_id,
+                match_id: match_data.id,
+                user,
+                team,
+                amount,
+                block_number: self.env().block_number(),
+                timestamp: self.env().block_timestamp(),
+                implied_odds,
+                withdrawn: 0,
+            });
+            let index = self.user_bet_count(match_data.id, user);
+            self.user_bets.insert((match_data.id, user, index), &bet_id);
+            self.user_bet_counts.insert((match_data.id, user), &(index + 1));
+            bet_id
+        }
+
+        // 内部函数: 记录撤注 / Internal: Record Bet Withdrawal
+        // 中文: 从最新的同向投注开始扣减（后进先出），使各笔投注的 amount - withdrawn 之和始终等于持仓。
+        // English: Charges the withdrawal against the newest bets on that side first (LIFO), so the sum of
+        //  amount - withdrawn over the bets always equals the position.
+        fn record_bet_withdrawal(&mut self, match_id: u128, user: AccountId, team: Team, amount: Balance) {
+            let mut remaining = amount;
+            let mut index = self.user_bet_count(match_id, user);
+            while remaining > 0 && index > 0 {
+                index -= 1;
+                let Some(bet_id) = self.user_bets.get(&(match_id, user, index)) else { continue };
+                let Some(mut bet) = self.bets.get(&bet_id) else { continue };
+                if bet.team != team {
+                    continue;
+                }
+                let taken = remaining.min(bet.amount - bet.withdrawn);
+                if taken > 0 {
+                    bet.withdrawn += taken;
+                    remaining -= taken;
+                    self.bets.insert(bet_id, &bet);
+                }
+            }
+        }
+
+        // 内部函数: 变更比赛状态 / Internal: Set Match Status
+        // 中文: 更新状态字段并把比赛从旧状态索引移到新状态索引；调用方负责写回比赛。
+        // English: Updates the status field and moves the match between status indexes; the caller still stores the match.
+        fn set_status(&mut self, match_data: &mut Match, status: MatchStatus) {
+            self.index_remove(MatchIndex::Status(match_data.status), match_data.id);
+            self.index_add(MatchIndex::Status(status), match_data.id);
+            match_data.status = status;
+        }
+
+        // 内部函数: 索引追加 / Internal: Append to Index
+        fn index_add(&mut self, index: MatchIndex, match_id: u128) {
+            let len = self.index_lens.get(&index).unwrap_or(0);
+            self.index_entries.insert((index, len), &match_id);
+            self.index_positions.insert((index, match_id), &len);
+            self.index_lens.insert(index, &(len + 1));
+        }
+
+        // 内部函数: 索引移除 / Internal: Remove from Index
+        // 中文: 用最后一个条目填补空位（swap-remove），保证 O(1) 开销。
+        // English: Fills the gap with the last entry (swap-remove) to keep the cost O(1).
+        fn index_remove(&mut self, index: MatchIndex, match_id: u128) {
+            let position = match self.index_positions.take(&(index, match_id)) {
+                Some(p) => p,
+                None => return,
+            };
+            let last = self.index_lens.get(&index).unwrap_or(0) - 1;
+            if position != last {
+                let moved = self.index_entries.get(&(index, last)).expect("Index corrupted");
+                self.index_entries.insert((index, position), &moved);
+                self.index_positions.insert((index, moved), &position);
+            }
+            self.index_entries.remove(&(index, last));
+            self.index_lens.insert(index, &last);
+        }
+
+        // 内部函数: 索引分页 / Internal: Page Through Index
+        fn index_ids(&self, index: MatchIndex, offset: u32, limit: u32) -> Vec<u128> {
+            let len = self.index_lens.get(&index).unwrap_or(0);
+            let end = len.min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            (offset..end)
+                .filter_map(|position| self.index_entries.get(&(index, position)))
+                .collect()
+        }
+
+        // 内部函数: 索引分页并读取比赛 / Internal: Page Through Index and Load Matches
+        fn index_page(&self, index: MatchIndex, offset: u32, limit: u32) -> Vec<Match> {
+            self.index_ids(index, offset, limit)
+                .into_iter()
+                .filter_map(|match_id| self.load_match(match_id))
+                .collect()
+        }
+
+        // 内部函数: 计算奖金 / Internal: Compute Payout
+        // 中文: 本金 + 按方向分配的奖池（赢70%/输30%/平局50%），若有首次注入者再扣5%手续费。返回 (手续费, 实得金额)。
+        //  零额持仓返回 (0, 0)。
+        // English: Principal + side share of the pool (win 70% / lose 30% / draw 50%), minus a 5% fee if a fee receiver exists.
+        //  Returns (fee, payout); a zero-amount position yields (0, 0).
+        fn compute_payout(&self, match_data: &Match, stake: &Stake) -> (Balance, Balance) {
+            if stake.amount == 0 {
+                return (0, 0); // 零额持仓无可派发 / Nothing to pay on a zero-amount position
+            }
+            let total_stake_team = if stake.team == Team::TeamA {
+                match_data.total_stake_a
+            } else {
+                match_data.total_stake_b
+            };
+            assert!(total_stake_team > 0, "No stakes for team");
+            self.try_compute_payout(match_data, stake).expect("Overflow")
+        }
+
+        // 内部函数: 是否可派发 / Internal: Is Payable
+        // 中文: 预先检查未领取方向的奖金都能算出，claim_many 据此跳过该比赛，而不是在 take_payout 中整体回滚。
+        // English: Pre-checks that every unclaimed position's payout can be computed, so claim_many can skip the match
+        //  instead of reverting the whole call inside take_payout.
+        fn is_payable(&self, match_data: &Match, stakes: &[Stake]) -> bool {
+            stakes
+                .iter()
+                .filter(|stake| !stake.claimed && stake.amount > 0)
+                .all(|stake| self.try_compute_payout(match_data, stake).is_some())
+        }
+
+        // 内部函数: 试算派奖 / Internal: Try Compute Payout
+        // 中文: 与 compute_payout 相同的计算，但溢出时返回 None 而非 panic，供只读查询使用。
+        // English: Same math as compute_payout but returns None on overflow instead of panicking; used by read-only queries.
+        fn try_compute_payout(&self, match_data: &Match, stake: &Stake) -> Option<(Balance, Balance)> {
+            let is_winner = match match_data.result {
+                MatchResult::TeamA => stake.team == Team::TeamA,
+                MatchResult::TeamB => stake.team == Team::TeamB,
+                MatchResult::Draw | MatchResult::None => true, // 平局双方均分 / draw -> both share equally
+            };
+            let total_stake_team = if stake.team == Team::TeamA {
+                match_data.total_stake_a
+            } else {
+                match_data.total_stake_b
+            };
+
+            let pool_share = if match_data.result == MatchResult::Draw {
+                match_data.pool_amount.checked_mul(50)? / 100  // 平分 / 50% split
+            } else if is_winner {
+                match_data.pool_amount.checked_mul(70)? / 100
+            } else {
+                match_data.pool_amount.checked_mul(30)? / 100
+            };
+            // 无同向总额，则仅返还本金 / No same-side total, return principal only
+            let user_pool = stake
+                .amount
+                .checked_mul(pool_share)?
+                .checked_div(total_stake_team)
+                .unwrap_or(0);
+            let user_share = stake.amount.checked_add(user_pool)?;
+
+            if self.fee_receiver.get(&match_data.id).is_some() {
+                let fee = user_share.checked_mul(FEE_BPS as Balance)? / BPS_DENOMINATOR as Balance;
+                Some((fee, user_share - fee))
+            } else {
+                // 无首次注入者，则不收取手续费 / No first injector -> no fee
+                Some((0, user_share))
+            }
+        }
+
+        // 内部函数: 用户投注列表 / Internal: User Stakes
+        // 中文: 返回用户在该比赛的已有方向投注，TeamA在前。
+        // English: Returns the user's existing positions in the match, TeamA first.
+        fn user_stakes(&self, match_id: u128, user: AccountId) -> Vec<Stake> {
+            [Team::TeamA, Team::TeamB]
+                .into_iter()
+                .filter_map(|team| self.stakes.get(&(match_id, user, team)))
+                .collect()
+        }
+
+        // 内部函数: 发放奖金 / Internal: Pay Out
+        // 中文: 结清用户在该比赛的全部未领取投注，并将实得金额转给 recipient。返回实得金额。
+        // English: Settles all of the user's unclaimed positions in the match and transfers the payout to `recipient`.
+        //  Returns the payout.
+        fn pay_out(&mut self, match_data: &Match, user: AccountId, recipient: AccountId) -> Balance {
+            let payout = self.take_payout(match_data, user);
+            self.env().transfer(recipient, payout).expect("Transfer failed");
+            payout
+        }
+
+        // 内部函数: 结清投注 / Internal: Take Payout
+        // 中文: 先把用户所有未领取方向标记为已领取（防重入），手续费合并转给接收者，返回尚未转出的实得金额，
+        //  由调用方决定转给谁、是否合并转账。
+        // English: Marks all of the user's unclaimed positions claimed (reentrancy guard) and sends the combined fee to the
+        //  fee receiver. Returns the payout, which the caller still has to transfer (possibly aggregated with others).
+        fn take_payout(&mut self, match_data: &Match, user: AccountId) -> Balance {
+            let (mut fee, mut payout): (Balance, Balance) = (0, 0);
+            for mut stake in self.user_stakes(match_data.id, user) {
+                if stake.claimed {
+                    continue;
+                }
+                let (f, p) = self.compute_payout(match_data, &stake);
+                fee = fee.checked_add(f).expect("Overflow");
+                payout = payout.checked_add(p).expect("Overflow");
+                stake.claimed = true;
+                self.stakes.insert((match_data.id, user, stake.team), &stake);
+            }
+            if let Some(mut summary) = self.settlement_summaries.get(&match_data.id) {
+                summary.claimed_amount = summary.claimed_amount.saturating_add(payout);
+                summary.claimed_fees = summary.claimed_fees.saturating_add(fee);
+                summary.claimed_count += 1;
+                self.settlement_summaries.insert(match_data.id, &summary);
+            }
+
+            self.total_liabilities = self.total_liabilities.saturating_sub(fee + payout);
+
+            self.update_stats(|stats| stats.total_paid_out = stats.total_paid_out.saturating_add(payout));
+            if fee > 0 {
+                if let Some(receiver) = self.fee_receiver.get(&match_data.id) {
+                    self.update_stats(|stats| stats.total_fees = stats.total_fees.saturating_add(fee));
+                    self.env().transfer(receiver, fee).expect("Fee transfer failed");
+                    let seq = self.next_event_seq(match_data.id);
+                    self.env().emit_event(FeePaid { match_id: match_data.id, seq, receiver, amount: fee });
+                }
+            }
+            payout
+        }
+
+        // 内部函数: 更新全局计数 / Internal: Update Global Counters
+        fn update_stats(&mut self, update: impl FnOnce(&mut StatsCounters)) {
+            let mut stats = self.stats.get().unwrap_or_default();
+            update(&mut stats);
+            self.stats.set(&stats);
+        }
+
+        // 内部函数: 记录投注量 / Internal: Record Stake Volume
+        // 中文: 累加投注总额，账户首次投注（单场或冠军盘）时计为新的独立投注者。
+        // English: Adds to the staked total and counts the account as a new unique bettor on its first stake, whether on a
+        //  match or an outright market.
+        fn record_stake_volume(&mut self, user: AccountId, amount: Balance) {
+            let first_stake = !self.known_bettors.contains(user);
+            if first_stake {
+                self.known_bettors.insert(user, &());
+            }
+            self.update_stats(|stats| {
+                stats.total_staked = stats.total_staked.saturating_add(amount);
+                stats.unique_bettors += u32::from(first_stake);
+            });
+        }
+
+        // 内部函数: 生成结算汇总 / Internal: Build Settlement Summary
+        // 中文: 可领取总额 = 双方本金 + 奖池中有人可领的部分，再按费率扣除手续费。
+        // English: Claimable gross = both sides' principal + the part of the pool someone can claim, less the fee.
+        fn build_settlement_summary(&self, match_data: &Match, unowed: Balance) -> SettlementSummary {
+            let (winning_stake, losing_stake) = match match_data.result {
+                MatchResult::TeamB => (match_data.total_stake_b, match_data.total_stake_a),
+                _ => (match_data.total_stake_a, match_data.total_stake_b),
+            };
+            let fee_bps = if self.fee_receiver.contains(match_data.id) { FEE_BPS } else { 0 };
+            let gross = (winning_stake + losing_stake + match_data.pool_amount).saturating_sub(unowed);
+            let expected_total_fees = gross.checked_mul(fee_bps as Balance).expect("Overflow") / BPS_DENOMINATOR as Balance;
+            SettlementSummary {
+                result: match_data.result,
+                winning_stake,
+                losing_stake,
+                pool_amount: match_data.pool_amount,
+                fee_bps,
+                expected_total_payout: gross - expected_total_fees,
+                expected_total_fees,
+                claimed_amount: 0,
+                claimed_fees: 0,
+                claimed_count: 0,
+                settled_at: self.env().block_timestamp(),
+            }
+        }
+
+        // 内部函数: 无人可领的奖池份额 / Internal: Unclaimable Pool Share
+        // 中文: 结算后，投注额为0的一方所对应的奖池份额（赢70%/输30%/平局各50%）永远无人领取。
+        // English: After settlement, the pool share assigned to a side with zero stakes (win 70% / lose 30% / draw 50% each)
+        //  can never be claimed.
+        fn unclaimable_pool_share(&self, match_data: &Match) -> Balance {
+            let (share_a, share_b) = match match_data.result {
+                MatchResult::TeamA => (70, 30),
+                MatchResult::TeamB => (30, 70),
+                MatchResult::Draw => (50, 50),
+                MatchResult::None => return 0,
+            };
+            let mut unowed: Balance = 0;
+            if match_data.total_stake_a == 0 {
+                unowed += (match_data.pool_amount * share_a) / 100;
+            }
+            if match_data.total_stake_b == 0 {
+                unowed += (match_data.pool_amount * share_b) / 100;
+            }
+            unowed
+        }
+    }
+
+    // =============================
+    // Unit Tests (#[ink::test])
+    // =============================
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test::DefaultAccounts;
+        use ink::env::DefaultEnvironment;
+
+        // 测试辅助: 使用独立的合约账户（余额为0），避免与默认账户alice重合。
+        // Test helpers: use a dedicated contract account (zero balance) instead of the default alice callee.
+        fn setup() -> (ParaGoalBetting, DefaultAccounts<DefaultEnvironment>) {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<DefaultEnvironment>(contract, 0);
+            set_caller(accounts.alice);
+            (ParaGoalBetting::new(world_cup_fixtures(), None, true), accounts)
+        }
+
+        // 世界杯1/4决赛：克罗地亚-巴西、荷兰-阿根廷、摩洛哥-葡萄牙、英格兰-法国
+        // World Cup quarter-finals: Croatia-Brazil, Netherlands-Argentina, Morocco-Portugal, England-France
+        fn world_cup_fixtures() -> Vec<(TeamId, TeamId, MatchMetadata)> {
+            [(0, 1), (2, 3), (4, 5), (6, 7)]
+                .into_iter()
+                .map(|(a, b)| (a, b, MatchMetadata::default()))
+                .collect()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<DefaultEnvironment>(caller);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+        }
+
+        // 以指定调用者转入资金（随后调用payable消息） / Transfer value in as `caller` ahead of a payable message
+        fn fund(caller: AccountId, value: Balance) {
+            ink::env::test::set_caller::<DefaultEnvironment>(caller);
+            ink::env::test::transfer_in::<DefaultEnvironment>(value);
+        }
+
+        type Event = <ParaGoalBetting as ink::reflect::ContractEventBase>::Type;
+
+        // 解码测试环境记录的全部事件 / Decode every event recorded by the test environment
+        fn recorded_events() -> Vec<(Event, usize)> {
+            ink::env::test::recorded_events()
+                .map(|e| {
+                    let event = <Event as scale::Decode>::decode(&mut &e.data[..]).expect("Invalid event");
+                    (event, e.topics.len())
+                })
+                .collect()
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<DefaultEnvironment>(account).unwrap_or_default()
+        }
+
+        // 创建并打开一场比赛，bob押A、charlie押B各100，alice注入1000奖池，结算为TeamA胜。
+        // Creates and opens a match, bob stakes 100 on A, charlie 100 on B, alice injects a 1000 pool, settled as TeamA.
+        fn settled_match(c: &mut ParaGoalBetting, accounts: &DefaultAccounts<DefaultEnvironment>) -> u128 {
+            set_caller(accounts.alice);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 100);
+            c.stake(id, Team::TeamA);
+            fund(accounts.charlie, 100);
+            c.stake(id, Team::TeamB);
+            fund(accounts.alice, 1000);
+            c.inject_pool(id);
+            set_caller(accounts.alice);
+            c.close_match(id);
+            c.settle_match(id, MatchResult::TeamA);
+            id
+        }
+
+        // 中文: 构造函数应初始化内置比赛，状态为Pending，且标记为内置。
+        // English: Constructor should initialize built-in matches with Pending status and built-in flag.
+        #[ink::test]
+        fn constructor_initializes_built_in_matches() {
+            let c = ParaGoalBetting::new(world_cup_fixtures(), None, true);
+            let m0 = c.get_match(0).expect("built-in match 0 should exist");
+            assert_eq!(m0.status, MatchStatus::Pending);
+            assert!(m0.is_built_in);
+        }
+
+        // 中文: 内置比赛由 system_admin 管理；空合约没有比赛，Owner 可随后追加内置比赛。
+        // English: Built-ins belong to system_admin; the empty constructor has no matches and the Owner can add
+        //  built-ins later.
+        #[ink::test]
+        fn configurable_built_in_fixtures() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller(accounts.alice);
+            let stage = MatchMetadata { stage: Some("Final".into()), ..Default::default() };
+            let c = ParaGoalBetting::new(vec![(3, 7, stage.clone())], Some(accounts.django), false);
+            assert_eq!(c.match_count(), 1);
+            let m = c.get_match(0).unwrap();
+            assert_eq!((m.admin, m.team_a, m.metadata), (accounts.django, 3, stage));
+        }
+
+        // 中文: 未选择 knockout 时，即使内置比赛数为2的幂也不会组成赛事。
+        // English: Without `knockout`, built-ins never form a tournament, even when their count is a power of two.
+        #[ink::test]
+        fn built_in_knockout_is_opt_in() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller(accounts.alice);
+            let fixtures = world_cup_fixtures().into_iter().take(2).collect();
+            let mut c = ParaGoalBetting::new(fixtures, None, false);
+            assert!(c.get_tournament(0).is_none());
+            assert_eq!(c.match_bracket_slot(0), None);
+            c.create_tournament("Cup".into(), vec![0, 1]);
+        }
+
+        #[ink::test]
+        fn empty_constructor_accepts_built_ins_later() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller(accounts.alice);
+            let mut c = ParaGoalBetting::new_empty();
+            assert_eq!(c.match_count(), 0);
+            assert_eq!(c.system_admin(), accounts.alice);
+            let id = c.add_built_in_match(0, 1, MatchMetadata::default());
+            let m = c.get_match(id).unwrap();
+            assert!(m.is_built_in);
+            assert_eq!(c.list_matches_by_built_in(true, 0, 10).len(), 1);
+        }
+
+        // 中文: Owner 更换 system_admin 后，内置比赛与之后新增的内置比赛都归新admin。
+        // English: After the Owner replaces system_admin, existing and future built-ins belong to the new admin.
+        #[ink::test]
+        fn owner_replaces_system_admin() {
+            let (mut c, accounts) = setup();
+            c.set_system_admin(accounts.django);
+            assert_eq!(c.system_admin(), accounts.django);
+            assert_eq!(c.list_matches_by_admin(accounts.django, 0, 10).len(), 4);
+            assert!(c.list_matches_by_admin(accounts.alice, 0, 10).is_empty());
+            assert!(matches!(
+                recorded_events().last(),
+                Some((Event::SystemAdminUpdated(e), 3)) if e.new_admin == accounts.django
+            ));
+            let id = c.add_built_in_match(0, 1, MatchMetadata::default());
+            assert_eq!(c.get_match(id).unwrap().admin, accounts.django);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Missing role")]
+        fn set_system_admin_requires_owner() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            c.set_system_admin(accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Missing role")]
+        fn add_built_in_match_requires_owner() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            c.add_built_in_match(0, 1, MatchMetadata::default());
+        }
+
+        // 中文: Admin创建自定义比赛后应能打开与关闭该比赛。
+        // English: Admin-created match can be opened and then closed.
+        #[ink::test]
+        fn admin_creates_opens_and_closes() {
+            let mut c = ParaGoalBetting::new(world_cup_fixtures(), None, true);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            assert_eq!(c.get_match(id).unwrap().status, MatchStatus::Open);
+            c.close_match(id);
+            assert_eq!(c.get_match(id).unwrap().status, MatchStatus::Closed);
+        }
+
+        // 中文: 任何人都可批量派发，奖金进入各自账户，已领取者被跳过。
+        // English: Anyone can distribute; payouts land in each bettor's own account and claimed stakes are skipped.
+        #[ink::test]
+        fn distribute_pays_each_user_and_skips_claimed() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let (bob_before, charlie_before) = (balance_of(accounts.bob), balance_of(accounts.charlie));
+            let alice_before = balance_of(accounts.alice);
+
+            set_caller(accounts.django);
+            let paid = c.distribute(id, vec![accounts.bob, accounts.charlie, accounts.eve]);
+            assert_eq!(paid, 2);
+            // bob: (100 + 700) - 5% = 760; charlie: (100 + 300) - 5% = 380
+            assert_eq!(balance_of(accounts.bob), bob_before + 760);
+            assert_eq!(balance_of(accounts.charlie), charlie_before + 380);
+            assert_eq!(balance_of(accounts.alice), alice_before + 40 + 20); // 手续费 / fees
+            assert!(c.get_user_stake(id, accounts.bob)[0].claimed);
+
+            assert_eq!(c.distribute(id, vec![accounts.bob, accounts.charlie]), 0);
+        }
+
+        // 中文: 列表查询覆盖全部、按状态、按管理员与按内置标志，并随状态变更更新。
+        // English: Listings by all, status, admin and built-in flag stay consistent across status transitions.
+        #[ink::test]
+        fn list_matches_by_filters() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            let a = c.create_match(1, 2, MatchMetadata::default());
+            let b = c.create_match(3, 4, MatchMetadata::default());
+            assert_eq!(c.match_count(), 6);
+            assert_eq!(c.list_matches(0, 100).len(), 6);
+            assert_eq!(c.list_matches(4, 1)[0].id, a);
+
+            let bob_ids: Vec<u128> = c.list_matches_by_admin(accounts.bob, 0, 10).iter().map(|m| m.id).collect();
+            assert_eq!(bob_ids, vec![a, b]);
+            assert_eq!(c.list_matches_by_built_in(true, 0, 10).len(), 4);
+            assert_eq!(c.list_matches_by_built_in(false, 0, 10).len(), 2);
+
+            c.open_match(a);
+            assert_eq!(c.list_matches_by_status(MatchStatus::Pending, 0, 10).len(), 5);
+            let open = c.list_matches_by_status(MatchStatus::Open, 0, 10);
+            assert_eq!(open.len(), 1);
+            assert_eq!(open[0].id, a);
+            c.close_match(a);
+            assert!(c.list_matches_by_status(MatchStatus::Open, 0, 10).is_empty());
+            assert_eq!(c.list_matches_by_status(MatchStatus::Closed, 0, 10)[0].id, a);
+        }
+
+        // 中文: 用户可同时押注双方，领取时汇总两个方向的奖金，查询返回全部持仓。
+        // English: A user may back both sides; claiming aggregates both positions and queries return every position.
+        #[ink::test]
+        fn hedged_user_claims_both_sides() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 100);
+            c.stake(id, Team::TeamA);
+            fund(accounts.bob, 100);
+            c.stake(id, Team::TeamB);
+            fund(accounts.charlie, 100);
+            c.stake(id, Team::TeamB);
+            fund(accounts.alice, 1000);
+            c.inject_pool(id);
+            set_caller(accounts.alice);
+            c.close_match(id);
+            c.settle_match(id, MatchResult::TeamA);
+
+            let positions = c.get_user_stake(id, accounts.bob);
+            assert_eq!(positions.len(), 2);
+            assert_eq!(positions[0].team, Team::TeamA);
+            assert_eq!(positions[1].team, Team::TeamB);
+            assert_eq!(c.staker_count(id), 2);
+            assert_eq!(c.list_stakers(id, 0, 10).len(), 3);
+
+            // A: (100 + 700) - 5% = 760; B: (100 + 150) - 12 = 238
+            assert_eq!(c.pending_payout(id, accounts.bob), 760 + 238);
+            let bob_before = balance_of(accounts.bob);
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            assert_eq!(balance_of(accounts.bob), bob_before + 998);
+            assert!(c.get_user_stake(id, accounts.bob).iter().all(|s| s.claimed));
+            assert_eq!(c.pending_payout(id, accounts.bob), 0);
+        }
+
+        // 中文: Resolver可以结算他人创建的比赛，撤销后失去该权限。
+        // English: A Resolver can settle someone else's match and loses that power once revoked.
+        #[ink::test]
+        fn resolver_role_settles_foreign_match() {
+            let (mut c, accounts) = setup();
+            assert!(c.has_role(Role::Owner, accounts.alice));
+            c.grant_role(Role::Resolver, accounts.eve);
+            c.grant_role(Role::MatchManager, accounts.eve);
+            assert!(c.has_role(Role::Resolver, accounts.eve));
+
+            set_caller(accounts.bob);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            set_caller(accounts.eve);
+            c.open_match(id);
+            c.close_match(id);
+            c.settle_match(id, MatchResult::Draw);
+            assert_eq!(c.get_match(id).unwrap().result, MatchResult::Draw);
 
-            assert!(self.deployer == self.env().caller(), "Only deployer");
-            let balance = self.env().balance();
-            assert!(amount <= balance, "Insufficient balance");
-            self.env().transfer(to, amount).expect("Transfer failed");
+            set_caller(accounts.alice);
+            c.revoke_role(Role::Resolver, accounts.eve);
+            assert!(!c.has_role(Role::Resolver, accounts.eve));
         }
-    }
 
-    // =============================
-    // Unit Tests (#[ink::test])
-    // =============================
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        #[ink::test]
+        #[should_panic(expected = "Missing role")]
+        fn only_owner_grants_roles() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            c.grant_role(Role::Treasurer, accounts.bob);
+        }
 
-        // 中文: 构造函数应初始化内置比赛，状态为Pending，且标记为内置。
-        // English: Constructor should initialize built-in matches with Pending status and built-in flag.
+        // 中文: 所有权两步转移：接受前旧所有者仍有效，接受后Owner角色转移。
+        // English: Two-step ownership: the old owner stays in charge until acceptance, then the Owner role moves.
         #[ink::test]
-        fn constructor_initializes_built_in_matches() {
-            let c = ParaGoalBetting::new();
-            let m0 = c.get_match(0).expect("built-in match 0 should exist");
-            assert_eq!(m0.status, MatchStatus::Pending);
-            assert!(m0.is_built_in);
+        fn ownership_transfer_is_two_step() {
+            let (mut c, accounts) = setup();
+            c.transfer_ownership(accounts.bob);
+            assert_eq!(c.pending_owner(), Some(accounts.bob));
+            assert_eq!(c.owner(), accounts.alice);
+            assert!(c.has_role(Role::Owner, accounts.alice));
+
+            set_caller(accounts.bob);
+            c.accept_ownership();
+            assert_eq!(c.owner(), accounts.bob);
+            assert_eq!(c.pending_owner(), None);
+            assert!(c.has_role(Role::Owner, accounts.bob));
+            assert!(!c.has_role(Role::Owner, accounts.alice));
+            // 内置比赛随所有权转移 / Built-in matches follow ownership
+            assert_eq!(c.system_admin(), accounts.bob);
+            assert_eq!(c.get_match(0).unwrap().admin, accounts.bob);
+            assert!(c.list_matches_by_admin(accounts.alice, 0, 10).is_empty());
+            c.set_stake_limits(0, StakeLimits::default());
         }
 
-        // 中文: Admin创建自定义比赛后应能打开与关闭该比赛。
-        // English: Admin-created match can be opened and then closed.
         #[ink::test]
-        fn admin_creates_opens_and_closes() {
-            let mut c = ParaGoalBetting::new();
-            let id = c.create_match([1u8;32], [2u8;32]);
+        fn owner_can_cancel_ownership_transfer() {
+            let (mut c, accounts) = setup();
+            c.transfer_ownership(accounts.bob);
+            c.cancel_ownership_transfer();
+            assert_eq!(c.pending_owner(), None);
+            assert!(matches!(
+                recorded_events().last(),
+                Some((Event::OwnershipTransferCancelled(e), _)) if e.pending_owner == accounts.bob
+            ));
+        }
+
+        // 中文: 比赛admin两步转移后，新admin可操作比赛，管理员索引同步更新。
+        // English: After a two-step admin transfer the new admin runs the match and the admin index follows.
+        #[ink::test]
+        fn match_admin_transfer_is_two_step() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.transfer_match_admin(id, accounts.charlie);
+            assert_eq!(c.pending_match_admin(id), Some(accounts.charlie));
+
+            set_caller(accounts.charlie);
+            c.accept_match_admin(id);
+            assert_eq!(c.get_match(id).unwrap().admin, accounts.charlie);
+            assert!(c.list_matches_by_admin(accounts.bob, 0, 10).is_empty());
+            assert_eq!(c.list_matches_by_admin(accounts.charlie, 0, 10)[0].id, id);
             c.open_match(id);
-            assert_eq!(c.get_match(id).unwrap().status, MatchStatus::Open);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not pending owner")]
+        fn only_pending_owner_accepts() {
+            let (mut c, accounts) = setup();
+            c.transfer_ownership(accounts.bob);
+            set_caller(accounts.charlie);
+            c.accept_ownership();
+        }
+
+        // 中文: 升级需先提案并等待时间锁；迁移在已是最新版本时直接完成，且不影响已有状态。
+        // English: Upgrades need a proposal and the timelock; migrate completes immediately on current storage and
+        //  leaves existing state intact.
+        #[ink::test]
+        fn upgrade_proposal_and_migration_keep_state() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let code_hash = Hash::from([7u8; 32]);
+            set_caller(accounts.alice);
+            c.propose_upgrade(code_hash);
+            let pending = c.pending_upgrade().expect("proposal stored");
+            assert_eq!(pending.eta, UPGRADE_TIMELOCK_MS);
+
+            assert_eq!(c.storage_version(), STORAGE_VERSION);
+            assert!(c.migrate(10));
+            let m = c.get_match(id).unwrap();
+            assert_eq!(m.status, MatchStatus::Settled);
+            assert_eq!(m.pool_amount, 1000);
+            assert_eq!(c.pending_payout(id, accounts.bob), 760);
+
+            c.cancel_upgrade();
+            assert_eq!(c.pending_upgrade(), None);
+        }
+
+        // 中文: 元数据在创建时设置，Pending 状态下admin可修改，开放后不可再改。
+        // English: Metadata is set at creation, editable by the admin while Pending and frozen once opened.
+        #[ink::test]
+        fn match_metadata_set_and_updated() {
+            let (mut c, _accounts) = setup();
+            let metadata = MatchMetadata {
+                competition_id: Some(2026),
+                stage: Some("Quarter-final".into()),
+                venue: Some("Lusail Stadium".into()),
+                kickoff: Some(1_800_000_000_000),
+                metadata_cid: Some("bafymeta".into()),
+            };
+            let id = c.create_match(0, 1, metadata.clone());
+            assert_eq!(c.get_match(id).unwrap().metadata, metadata);
+            assert_eq!(c.get_match(0).unwrap().metadata, MatchMetadata::default());
+
+            let updated = MatchMetadata { venue: Some("Al Bayt Stadium".into()), ..metadata };
+            c.update_match_metadata(id, updated.clone());
+            assert_eq!(c.get_match(id).unwrap().metadata, updated);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Can only update in Pending")]
+        fn match_metadata_frozen_after_open() {
+            let (mut c, _accounts) = setup();
+            let id = c.create_match(0, 1, MatchMetadata::default());
+            c.open_match(id);
+            c.update_match_metadata(id, MatchMetadata { stage: Some("Final".into()), ..Default::default() });
+        }
+
+        // 中文: 内置1/4决赛构成赛事0；两场分出胜负后自动创建半决赛，平局由admin指定晋级者，决赛胜者为冠军。
+        // English: The built-in quarter-finals form tournament 0; a semi-final appears once both feeders are decided,
+        //  draws are resolved by the admin and the final's winner becomes champion.
+        #[ink::test]
+        fn knockout_bracket_progresses() {
+            let (mut c, _accounts) = setup();
+            let t = c.get_tournament(0).unwrap();
+            assert_eq!((t.rounds, t.slot_count, t.champion), (3, 7, None));
+            assert_eq!(c.get_bracket(0).len(), 7);
+            assert_eq!(c.match_bracket_slot(2), Some((0, 2)));
+
+            let play = |c: &mut ParaGoalBetting, id: u128, result: MatchResult| {
+                c.open_match(id);
+                c.close_match(id);
+                c.settle_match(id, result);
+            };
+            play(&mut c, 0, MatchResult::TeamB);  // 巴西 / Brazil
+            assert_eq!(c.get_bracket(0)[4].match_id, None);
+            play(&mut c, 1, MatchResult::Draw);
+            c.advance_winner(0, 1, 3);             // 阿根廷点球晋级 / Argentina on penalties
+            let semi = c.get_bracket(0)[4].clone();
+            assert_eq!((semi.team_a, semi.team_b), (Some(1), Some(3)));
+            let semi_id = semi.match_id.unwrap();
+            let m = c.get_match(semi_id).unwrap();
+            assert_eq!((m.team_a, m.team_b, m.metadata.competition_id), (1, 3, None));
+            assert_eq!(c.match_bracket_slot(semi_id), Some((0, 4)));
+
+            play(&mut c, 2, MatchResult::TeamB);
+            play(&mut c, 3, MatchResult::TeamB);
+            let other_semi = c.get_bracket(0)[5].match_id.unwrap();
+            play(&mut c, semi_id, MatchResult::TeamB);
+            play(&mut c, other_semi, MatchResult::TeamA);
+            let final_id = c.get_bracket(0)[6].match_id.unwrap();
+            assert_eq!(c.get_match(final_id).map(|m| (m.team_a, m.team_b)), Some((3, 5)));
+            play(&mut c, final_id, MatchResult::TeamA);
+            assert_eq!(c.get_tournament(0).unwrap().champion, Some(3));
+        }
+
+        // 中文: 冠军盘在决赛结算时自动结算；押中冠军者分70%奖池，其余投注者分30%，均扣5%手续费。
+        // English: The outright market settles with the final; champion backers share 70% of the pool, the rest 30%,
+        //  each less the 5% fee.
+        #[ink::test]
+        fn outright_market_settles_with_final() {
+            let (mut c, accounts) = setup();
+            c.open_outright_market(0, 1_000);
+            fund(accounts.bob, 100);
+            c.stake_outright(0, 3);  // 阿根廷 / Argentina
+            fund(accounts.charlie, 300);
+            c.stake_outright(0, 1);  // 巴西 / Brazil
+            fund(accounts.alice, 1000);
+            c.inject_outright_pool(0);
+            assert_eq!(c.outright_team_total(0, 3), 100);
+            assert_eq!(c.get_outright_market(0).unwrap().total_staked, 400);
+
+            set_caller(accounts.alice);
+            let play = |c: &mut ParaGoalBetting, id: u128, result: MatchResult| {
+                c.open_match(id);
+                c.close_match(id);
+                c.settle_match(id, result);
+            };
+            // 1/4决赛客队全胜，阿根廷夺冠 / Away sides win every quarter-final, Argentina take the title
+            let results = [MatchResult::TeamB, MatchResult::TeamB, MatchResult::TeamB, MatchResult::TeamB,
+                           MatchResult::TeamB, MatchResult::TeamA, MatchResult::TeamA];
+            for (slot, result) in results.into_iter().enumerate() {
+                let id = c.get_bracket(0)[slot].match_id.unwrap();
+                play(&mut c, id, result);
+            }
+            assert_eq!(c.get_outright_market(0).unwrap().winner, Some(3));
+
+            // bob: (100 + 700) * 95% = 760；charlie: (300 + 300) * 95% = 570
+            set_caller(accounts.bob);
+            assert_eq!(c.claim_outright(0), 760);
+            set_caller(accounts.charlie);
+            assert_eq!(c.claim_outright(0), 570);
+            assert_eq!(c.total_liabilities(), 0);
+            assert!(c.get_outright_stakes(0, accounts.charlie)[0].claimed);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Market locked")]
+        fn outright_stake_rejected_after_lock() {
+            let (mut c, accounts) = setup();
+            c.open_outright_market(0, 1_000);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            fund(accounts.bob, 100);
+            c.stake_outright(0, 3);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Team not in tournament")]
+        fn outright_stake_requires_participant() {
+            let (mut c, accounts) = setup();
+            let japan = c.register_team("Japan".into(), "JPN".into(), "JP".into(), String::new());
+            c.open_outright_market(0, 1_000);
+            fund(accounts.bob, 100);
+            c.stake_outright(0, japan);
+        }
+
+        // 中文: 与比赛一致，Owner 可代任何赛事admin操作。
+        // English: As with matches, an Owner can act for any tournament admin.
+        #[ink::test]
+        fn owner_can_act_as_tournament_admin() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            set_caller(accounts.alice);
+            c.open_outright_market(t, 1_000);
+            assert_eq!(c.get_outright_market(t).unwrap().lock_time, 1_000);
+        }
+
+        // 中文: 修改首轮比赛的队伍会同步对阵表，冠军盘参赛队随之变化。
+        // English: Changing a first-round match's teams updates the bracket, and with it the outright participants.
+        #[ink::test]
+        fn first_round_team_update_syncs_bracket() {
+            let (mut c, _accounts) = setup();
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            c.update_match_teams(a, 4, 2);
+            let slot = c.get_bracket(t)[0].clone();
+            assert_eq!((slot.team_a, slot.team_b), (Some(4), Some(2)));
+            assert!(c.is_tournament_team(t, 4));
+            assert!(!c.is_tournament_team(t, 0));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Teams set by bracket")]
+        fn later_round_teams_cannot_be_updated() {
+            let (mut c, _accounts) = setup();
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            for id in [a, b] {
+                c.open_match(id);
+                c.close_match(id);
+                c.settle_match(id, MatchResult::TeamA);
+            }
+            let final_id = c.get_bracket(t)[2].match_id.unwrap();
+            c.update_match_teams(final_id, 0, 2);
+        }
+
+        // 两场首轮比赛组成的赛事，并开启锁盘时间为1000的冠军盘 / Two-match cup with an outright market locking at 1000
+        fn outright_cup(c: &mut ParaGoalBetting) -> (TournamentId, u128, u128) {
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            c.open_outright_market(t, 1_000);
+            (t, a, b)
+        }
+
+        // 中文: 平局逾期未指定晋级者时对阵表卡住，锁盘后可手动结算冠军盘。
+        // English: A draw left unresolved past the window makes the bracket stuck, so the locked market can be settled
+        //  by hand.
+        #[ink::test]
+        fn outright_settles_manually_when_stuck() {
+            let (mut c, accounts) = setup();
+            let (t, a, _) = outright_cup(&mut c);
+            fund(accounts.bob, 100);
+            c.stake_outright(t, 0);
+            set_caller(accounts.alice);
+            c.open_match(a);
+            c.close_match(a);
+            c.settle_match(a, MatchResult::Draw);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(DRAW_RESOLUTION_WINDOW_MS);
+            c.settle_outright(t, 0);
+            assert_eq!(c.get_outright_market(t).unwrap().winner, Some(0));
+            set_caller(accounts.bob);
+            assert_eq!(c.claim_outright(t), 100);
+        }
+
+        // 中文: 正常推进中的对阵表（比赛尚未进行）即使已锁盘也不能手动结算。
+        // English: A bracket progressing normally (matches not yet played) cannot be settled by hand, even once locked.
+        #[ink::test]
+        #[should_panic(expected = "Bracket not stuck")]
+        fn outright_manual_settle_refused_while_progressing() {
+            let (mut c, _accounts) = setup();
+            let (t, _, _) = outright_cup(&mut c);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            c.settle_outright(t, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Bracket already started")]
+        fn outright_market_rejected_after_bracket_settles() {
+            let (mut c, _accounts) = setup();
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            c.open_match(a);
+            c.close_match(a);
+            c.settle_match(a, MatchResult::TeamA);
+            c.open_outright_market(t, 1_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Lock after kickoff")]
+        fn outright_lock_must_precede_kickoff() {
+            let (mut c, _accounts) = setup();
+            let kickoff = MatchMetadata { kickoff: Some(500), ..Default::default() };
+            let a = c.create_match(0, 2, kickoff);
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let t = c.create_tournament("Cup".into(), vec![a, b]);
+            c.open_outright_market(t, 1_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Market not locked")]
+        fn outright_manual_settle_waits_for_lock() {
+            let (mut c, _accounts) = setup();
+            let (t, _, _) = outright_cup(&mut c);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(999);
+            c.settle_outright(t, 0);
+        }
+
+        // 中文: 平局在处理期限内等待 advance_winner 时对阵表未卡住，不可手动结算。
+        // English: A draw still within its window to be resolved by advance_winner does not make the bracket stuck, so
+        //  manual settlement is refused.
+        #[ink::test]
+        #[should_panic(expected = "Bracket not stuck")]
+        fn outright_manual_settle_requires_stuck_bracket() {
+            let (mut c, _accounts) = setup();
+            let (t, a, b) = outright_cup(&mut c);
+            for (id, result) in [(a, MatchResult::Draw), (b, MatchResult::TeamA)] {
+                c.open_match(id);
+                c.close_match(id);
+                c.settle_match(id, result);
+            }
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            c.settle_outright(t, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid bracket size")]
+        fn create_tournament_requires_power_of_two() {
+            let (mut c, _accounts) = setup();
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            let b = c.create_match(1, 3, MatchMetadata::default());
+            let d = c.create_match(4, 6, MatchMetadata::default());
+            c.create_tournament("Cup".into(), vec![a, b, d]);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Match already in tournament")]
+        fn create_tournament_rejects_bracketed_match() {
+            let (mut c, _accounts) = setup();
+            let a = c.create_match(0, 2, MatchMetadata::default());
+            c.create_tournament("Cup".into(), vec![a, 0]);
+        }
+
+        // 中文: 删除会清除比赛及其索引；归档的比赛从默认列表移到归档列表。
+        // English: Deleting clears the match and its index entries; archiving moves a match from the default listing
+        //  to the archived one.
+        #[ink::test]
+        fn delete_and_archive_matches() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(0, 1, MatchMetadata::default());
+            c.set_stake_limits(id, StakeLimits { min_stake: 5, ..Default::default() });
+            assert_eq!(c.list_matches(0, 100).len(), 5);
+            c.delete_match(id);
+            assert!(c.get_match(id).is_none());
+            assert_eq!(c.list_matches(0, 100).len(), 4);
+            assert_eq!(c.match_count(), 4);
+            assert!(c.list_matches_by_admin(accounts.alice, 0, 100).iter().all(|m| m.id != id));
+            assert!(c.list_matches_by_status(MatchStatus::Pending, 0, 100).iter().all(|m| m.id != id));
+            assert_eq!(c.get_stake_limits(id), StakeLimits::default());
+
+            let settled = settled_match(&mut c, &accounts);
+            c.archive_match(settled);
+            assert!(c.get_match(settled).unwrap().archived);
+            assert!(c.list_matches(0, 100).iter().all(|m| m.id != settled));
+            assert_eq!(c.list_archived_matches(0, 100)[0].id, settled);
+            assert_eq!(c.match_count(), 4);
+            assert!(c.list_matches_by_status(MatchStatus::Settled, 0, 100).iter().any(|m| m.id == settled));
+            // 归档不影响领取 / Archiving does not block claims
+            set_caller(accounts.bob);
+            c.claim_payout(settled);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Cannot delete built-in match")]
+        fn built_in_match_cannot_be_deleted() {
+            let (mut c, _accounts) = setup();
+            c.delete_match(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Match has funds")]
+        fn match_with_pool_cannot_be_deleted() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(0, 1, MatchMetadata::default());
+            fund(accounts.alice, 100);
+            c.inject_pool(id);
+            set_caller(accounts.alice);
+            c.delete_match(id);
+        }
+
+        // 中文: 开放、注入（含手续费接收者）、领取手续费与代领都会发出事件，match_id/user/admin 作为主题。
+        // English: Opening, injecting (with the fee receiver), fee payment and admin withdrawal all emit events with
+        //  match_id/user/admin as topics.
+        #[ink::test]
+        fn lifecycle_emits_indexed_events() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let events = recorded_events();
+            assert!(events.iter().any(|(e, topics)| {
+                matches!(e, Event::MatchOpened(MatchOpened { match_id, by, .. }) if *match_id == id && *by == accounts.alice)
+                    && *topics == 2
+            }));
+            assert!(events.iter().any(|(e, _)| matches!(
+                e,
+                Event::PoolInjected(PoolInjected { match_id, fee_receiver, amount: 1000, .. })
+                    if *match_id == id && *fee_receiver == accounts.alice
+            )));
+
+            set_caller(accounts.alice);
+            c.withdraw_unclaimed(id, accounts.bob);
+            let events = recorded_events();
+            let n = events.len();
+            assert!(matches!(
+                &events[n - 2],
+                (Event::FeePaid(FeePaid { match_id, receiver, amount: 40, .. }), 3)
+                    if *match_id == id && *receiver == accounts.alice
+            ));
+            assert!(matches!(
+                &events[n - 1],
+                (Event::UnclaimedWithdrawn(UnclaimedWithdrawn { match_id, user, admin, amount: 760, .. }), 4)
+                    if *match_id == id && *user == accounts.bob && *admin == accounts.alice
+            ));
+        }
+
+        // 中文: 全局统计随投注、注入、撤注与领取增量更新，各状态比赛数来自索引。
+        // English: Global stats follow stakes, injections, early exits and claims; per-status counts come from the indexes.
+        #[ink::test]
+        fn global_stats_track_volume() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let other = c.create_match(2, 3, MatchMetadata::default());
+            c.open_match(other);
+            fund(accounts.bob, 200);
+            c.stake(other, Team::TeamA);
+            // 罚金5%留在奖池 / The 5% penalty stays in the pool
+            c.withdraw_stake(other, Team::TeamA, 100);
+
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            let stats = c.get_global_stats();
+            assert_eq!((stats.total_staked, stats.total_injected, stats.total_refunded), (400, 1000, 95));
+            assert_eq!((stats.total_paid_out, stats.total_fees), (760, 40));
+            assert_eq!(stats.unique_bettors, 2);
+            assert_eq!((stats.pending_matches, stats.open_matches, stats.settled_matches), (4, 1, 1));
+            assert_eq!(stats.closed_matches, 0);
+        }
+
+        // 中文: 结算时写入汇总，领取后更新已领取计数。
+        // English: The summary is written at settlement and its claim counters follow each claim.
+        #[ink::test]
+        fn settlement_summary_tracks_claims() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let summary = c.get_settlement_summary(id).expect("summary stored");
+            assert_eq!((summary.winning_stake, summary.losing_stake, summary.pool_amount), (100, 100, 1000));
+            assert_eq!(summary.fee_bps, FEE_BPS);
+            // (100 + 100 + 1000) * 95% = 1140 = 760 + 380
+            assert_eq!((summary.expected_total_payout, summary.expected_total_fees), (1140, 60));
+            assert_eq!(summary.claimed_count, 0);
+            assert!(recorded_events().iter().any(|(e, _)| matches!(
+                e,
+                Event::MatchSettled(e) if e.match_id == id && e.expected_total_payout == 1140 && e.winning_stake == 100
+            )));
+
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            let summary = c.get_settlement_summary(id).unwrap();
+            assert_eq!((summary.claimed_amount, summary.claimed_fees, summary.claimed_count), (760, 40, 1));
+            assert_eq!(c.get_settlement_summary(0), None);
+        }
+
+        // 中文: 每场比赛的事件序号连续递增，不同比赛互不影响。
+        // English: Event sequence numbers increase by one per match and are independent across matches.
+        #[ink::test]
+        fn match_events_carry_sequence_numbers() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let seqs: Vec<u64> = recorded_events()
+                .into_iter()
+                .filter_map(|(e, _)| match e {
+                    Event::MatchCreated(e) if e.match_id == id => Some(e.seq),
+                    Event::MatchOpened(e) if e.match_id == id => Some(e.seq),
+                    Event::Staked(e) if e.match_id == id => Some(e.seq),
+                    Event::PoolInjected(e) if e.match_id == id => Some(e.seq),
+                    Event::MatchClosed(e) if e.match_id == id => Some(e.seq),
+                    Event::MatchSettled(e) if e.match_id == id => Some(e.seq),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(seqs, vec![1, 2, 3, 4, 5, 6, 7]);
+            assert_eq!(c.match_event_seq(id), 7);
+            assert_eq!(c.match_event_seq(0), 1);
+
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            // 手续费 + 领取 / Fee payment + claim
+            assert_eq!(c.match_event_seq(id), 9);
+        }
+
+        #[ink::test]
+        fn config_changes_emit_events() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(0, 1, MatchMetadata::default());
+            let limits = StakeLimits { min_stake: 10, ..Default::default() };
+            c.set_stake_limits(id, limits.clone());
+            c.set_early_exit_penalty(250);
+            c.set_max_account_exposure(Some(500));
+            let events = recorded_events();
+            let n = events.len();
+            assert!(matches!(&events[n - 3], (Event::StakeLimitsUpdated(e), 2) if e.match_id == id && e.limits == limits));
+            assert!(matches!(&events[n - 2], (Event::EarlyExitPenaltyUpdated(e), 1) if e.penalty_bps == 250));
+            assert!(matches!(
+                &events[n - 1],
+                (Event::AccountExposureCapUpdated(e), 1) if e.cap == Some(500) && e.by == accounts.alice
+            ));
+        }
+
+        // 中文: 旧版本（bytes32 队伍）记录在迁移时补齐注册表并转换为队伍ID。
+        // English: Legacy bytes32 records get the registry seeded and their teams converted to ids on migration.
+        #[ink::test]
+        fn migrate_converts_legacy_matches() {
+            let (mut c, accounts) = setup();
+            let legacy = MatchV1 {
+                id: 3,
+                admin: accounts.alice,
+                team_a: [2u8; 32],
+                team_b: [9u8; 32],
+                is_built_in: true,
+                pool_injected_by: None,
+                pool_amount: 0,
+                status: MatchStatus::Pending,
+                result: MatchResult::None,
+                total_stake_a: 0,
+                total_stake_b: 0,
+                paused: false,
+            };
+            c.matches.insert(3, &VersionedMatch::V1(legacy));
+            c.storage_version = 1;
+            c.teams = Mapping::default();
+            c.team_by_code = Mapping::default();
+            c.next_team_id = Lazy::new();
+            set_caller(accounts.alice);
+
+            assert!(!c.migrate(2));
+            assert!(!c.matches.get(&3).unwrap().is_latest());
+            assert!(c.migrate(10));
+            assert_eq!(c.storage_version(), STORAGE_VERSION);
+            assert!(c.matches.get(&3).unwrap().is_latest());
+            let m = c.get_match(3).unwrap();
+            assert_eq!((m.team_a, m.team_b), (2, UNKNOWN_TEAM));
+            assert_eq!(m.metadata, MatchMetadata::default());
+            assert!(!m.archived);
+            assert_eq!(c.get_team(2).unwrap().name, "Netherlands");
+            // 迁移完成后恢复正常使用 / Normal use resumes once migrated
+            let id = c.create_match(2, 3, MatchMetadata::default());
+            assert_eq!(c.get_match(id).unwrap().team_b, 3);
+        }
+
+        // 中文: 升级后、迁移完成前，修改状态的消息被拒绝，读取仍可用。
+        // English: Between upgrade and migration, state-changing messages are rejected while reads still work.
+        #[ink::test]
+        #[should_panic(expected = "Migration pending")]
+        fn migration_pending_blocks_state_changes() {
+            let (mut c, _accounts) = setup();
+            c.storage_version = 1;
+            assert_eq!(c.get_match(0).unwrap().team_a, 0);
+            c.create_match(0, 1, MatchMetadata::default());
+        }
+
+        #[ink::test]
+        fn team_registry_validates_matches() {
+            let (mut c, accounts) = setup();
+            assert_eq!(c.team_count(), 8);
+            assert_eq!(c.get_match(0).map(|m| (m.team_a, m.team_b)), Some((0, 1)));
+            let id = c.register_team("Japan".into(), "JPN".into(), "JP".into(), "bafylogo".into());
+            assert_eq!(c.team_by_code("JPN".into()), Some(id));
+            assert_eq!(c.get_team(id).unwrap().logo_cid, "bafylogo");
+            set_caller(accounts.bob);
+            let m = c.create_match(id, 1, MatchMetadata::default());
+            assert_eq!(c.get_match(m).unwrap().team_a, id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Teams must differ")]
+        fn create_match_rejects_same_team() {
+            let (mut c, _accounts) = setup();
+            c.create_match(1, 1, MatchMetadata::default());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Team code taken")]
+        fn duplicate_team_code_rejected() {
+            let (mut c, _accounts) = setup();
+            c.register_team("Brasil".into(), "BRA".into(), "BR".into(), String::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Team not found")]
+        fn create_match_requires_registered_teams() {
+            let (mut c, _accounts) = setup();
+            c.create_match(0, 99, MatchMetadata::default());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Upgrade timelocked")]
+        fn upgrade_respects_timelock() {
+            let (mut c, _accounts) = setup();
+            let code_hash = Hash::from([7u8; 32]);
+            c.propose_upgrade(code_hash);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(UPGRADE_TIMELOCK_MS - 1);
+            c.upgrade(code_hash);
+        }
+
+        // 中文: 审计结果在整个比赛生命周期内保持余额覆盖负债。
+        // English: The solvency audit shows the balance covering liabilities throughout a match lifecycle.
+        #[ink::test]
+        fn audit_solvency_tracks_lifecycle() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            let report = c.audit_solvency();
+            assert_eq!(report.balance, 1200);
+            assert_eq!(report.liabilities, 1200);
+            assert!(report.solvent);
+
+            set_caller(accounts.django);
+            c.distribute(id, vec![accounts.bob, accounts.charlie]);
+            let report = c.audit_solvency();
+            // 全部领取后负债清零 / Liabilities drop to zero once everyone is paid
+            assert_eq!(report.liabilities, 0);
+            assert_eq!(report.balance, 0);
+            assert!(report.solvent);
+        }
+
+        // 中文: 紧急提取只能动用超出负债的盈余，领取后负债同步减少。
+        // English: Emergency withdrawal is limited to the surplus above liabilities, which shrink as payouts are claimed.
+        #[ink::test]
+        fn emergency_withdraw_limited_to_surplus() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            assert_eq!(c.total_liabilities(), 1200);
+            // 额外的捐赠资金构成盈余 / Extra donated funds form the surplus
+            fund(accounts.alice, 50);
+            set_caller(accounts.alice);
+            c.emergency_withdraw(accounts.eve, 50);
+            assert_eq!(balance_of(accounts.eve), 50);
+
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            assert_eq!(c.total_liabilities(), 1200 - 800);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exceeds surplus")]
+        fn emergency_withdraw_cannot_touch_user_funds() {
+            let (mut c, accounts) = setup();
+            settled_match(&mut c, &accounts);
+            set_caller(accounts.alice);
+            c.emergency_withdraw(accounts.eve, 1);
+        }
+
+        // 中文: 暂停权限账户可暂停单场比赛，恢复后可继续领取；claim_many 会跳过暂停的比赛。
+        // English: A pauser can freeze a single match and later resume it; claim_many skips paused matches.
+        #[ink::test]
+        fn pauser_can_pause_and_resume_match() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            c.grant_role(Role::Pauser, accounts.django);
+            set_caller(accounts.django);
+            c.pause_match(id);
+            assert!(c.get_match(id).unwrap().paused);
+            set_caller(accounts.bob);
+            assert_eq!(c.claim_many(vec![id]), vec![(id, ClaimOutcome::Paused)]);
+
+            set_caller(accounts.django);
+            c.unpause_match(id);
+            set_caller(accounts.bob);
+            assert_eq!(c.claim_many(vec![id]), vec![(id, ClaimOutcome::Paid(760))]);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Contract paused")]
+        fn global_pause_blocks_staking() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            c.pause();
+            assert!(c.is_paused());
+            fund(accounts.bob, 10);
+            c.stake(id, Team::TeamA);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Contract paused")]
+        fn global_pause_blocks_admin_configuration() {
+            let (mut c, _) = setup();
+            c.pause();
+            c.set_early_exit_penalty(100);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Match paused")]
+        fn match_pause_blocks_admin_transfer() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.pause_match(id);
+            c.transfer_match_admin(id, accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Missing role")]
+        fn non_pauser_cannot_pause() {
+            let (mut c, accounts) = setup();
+            set_caller(accounts.bob);
+            c.pause();
+        }
+
+        // 限额为 最小10 / 单用户100 / 总额150 的已开启比赛 / Open match limited to min 10, 100 per user, 150 total
+        fn limited_match(c: &mut ParaGoalBetting, accounts: &DefaultAccounts<DefaultEnvironment>) -> u128 {
+            set_caller(accounts.alice);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.set_stake_limits(id, StakeLimits {
+                min_stake: 10,
+                max_stake_per_user: Some(100),
+                max_total_pool: Some(150),
+            });
+            c.open_match(id);
+            fund(accounts.bob, 60);
+            c.stake(id, Team::TeamA);
+            fund(accounts.bob, 40);
+            c.stake(id, Team::TeamB);
+            id
+        }
+
+        // 中文: 每场限额内的投注（含对冲）被接受，账户敞口按所有Open比赛累计，比赛关闭后即释放。
+        // English: Stakes within the per-match limits (hedges included) pass; exposure sums over open matches and is
+        //  released as soon as a match closes.
+        #[ink::test]
+        fn stake_limits_accept_stakes_within_bounds() {
+            let (mut c, accounts) = setup();
+            let id = limited_match(&mut c, &accounts);
+            assert_eq!(c.get_stake_limits(id).max_total_pool, Some(150));
+            assert_eq!(c.account_exposure(accounts.bob), 100);
+
+            set_caller(accounts.alice);
+            c.set_max_account_exposure(Some(120));
+            let other = c.create_match(3, 4, MatchMetadata::default());
+            c.open_match(other);
+            fund(accounts.bob, 20);
+            c.stake(other, Team::TeamA);
+            assert_eq!(c.account_exposure(accounts.bob), 120);
+            set_caller(accounts.alice);
             c.close_match(id);
-            assert_eq!(c.get_match(id).unwrap().status, MatchStatus::Closed);
+            assert_eq!(c.account_exposure(accounts.bob), 20);
+            // 未领取的已结算比赛不占用敞口 / An unclaimed settled match does not hold exposure
+            c.settle_match(id, MatchResult::TeamA);
+            fund(accounts.bob, 100);
+            c.stake(other, Team::TeamB);
+            assert_eq!(c.account_exposure(accounts.bob), 120);
+            assert_eq!(c.exposure_match_counts.get(&accounts.bob), Some(1));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid stake limits")]
+        fn set_stake_limits_rejects_min_above_max() {
+            let (mut c, _) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.set_stake_limits(id, StakeLimits { min_stake: 50, max_stake_per_user: Some(10), max_total_pool: None });
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Below minimum stake")]
+        fn stake_below_minimum_rejected() {
+            let (mut c, accounts) = setup();
+            let id = limited_match(&mut c, &accounts);
+            fund(accounts.charlie, 5);
+            c.stake(id, Team::TeamA);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exceeds max stake per user")]
+        fn stake_above_per_user_cap_rejected() {
+            let (mut c, accounts) = setup();
+            let id = limited_match(&mut c, &accounts);
+            fund(accounts.bob, 10);
+            c.stake(id, Team::TeamA);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exceeds max total pool")]
+        fn stake_above_total_cap_rejected() {
+            let (mut c, accounts) = setup();
+            let id = limited_match(&mut c, &accounts);
+            fund(accounts.charlie, 60);
+            c.stake(id, Team::TeamA);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exceeds account exposure cap")]
+        fn account_exposure_cap_rejects_stake() {
+            let (mut c, accounts) = setup();
+            c.set_max_account_exposure(Some(100));
+            let a = c.create_match(1, 2, MatchMetadata::default());
+            let b = c.create_match(3, 4, MatchMetadata::default());
+            c.open_match(a);
+            c.open_match(b);
+            fund(accounts.bob, 80);
+            c.stake(a, Team::TeamA);
+            fund(accounts.bob, 30);
+            c.stake(b, Team::TeamB);
+        }
+
+        // 中文: 每次投注单独记录，包含区块信息与下注时的隐含赔率，汇总金额仍可查询。
+        // English: Each stake is recorded separately with block info and implied odds, while the aggregate stays available.
+        #[ink::test]
+        fn stake_records_bet_history() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            fund(accounts.alice, 1000);
+            c.inject_pool(id);
+            set_caller(accounts.alice);
+            c.open_match(id);
+
+            fund(accounts.bob, 100);
+            let first = c.stake(id, Team::TeamA);
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            fund(accounts.bob, 100);
+            let second = c.stake(id, Team::TeamA);
+            assert_ne!(first, second);
+
+            let bets = c.get_user_bets(id, accounts.bob, 0, 10);
+            assert_eq!(bets.len(), 2);
+            // (100 + 700) / 100 = 8x, then (200 + 700) / 200 = 4.5x
+            assert_eq!(bets[0].implied_odds, 8 * ODDS_PRECISION);
+            assert_eq!(bets[1].implied_odds, 45_000);
+            assert!(bets[1].block_number > bets[0].block_number);
+            assert_eq!(c.get_bet(second), Some(bets[1].clone()));
+            assert_eq!(c.get_user_stake(id, accounts.bob)[0].amount, 200);
+        }
+
+        // 中文: Open期间可提前撤注，罚金并入奖池，方向总额同步减少。
+        // English: Stakes can be withdrawn while Open; the penalty joins the pool and side totals shrink.
+        #[ink::test]
+        fn withdraw_stake_applies_penalty_to_pool() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 120);
+            c.stake(id, Team::TeamB);
+            fund(accounts.bob, 80);
+            c.stake(id, Team::TeamB);
+
+            let bob_before = balance_of(accounts.bob);
+            set_caller(accounts.bob);
+            let refund = c.withdraw_stake(id, Team::TeamB, 100);
+            assert_eq!(refund, 95); // 5% 默认罚金 / 5% default penalty
+            assert_eq!(balance_of(accounts.bob), bob_before + 95);
+            let m = c.get_match(id).unwrap();
+            assert_eq!(m.total_stake_b, 100);
+            assert_eq!(m.pool_amount, 5);
+            assert_eq!(c.get_user_stake(id, accounts.bob)[0].amount, 100);
+            let bets = c.get_user_bets(id, accounts.bob, 0, 10);
+            // 后进先出扣减 / Charged newest-first
+            assert_eq!((bets[0].withdrawn, bets[1].withdrawn), (20, 80));
+            let open: Balance = bets
+                .iter()
+                .map(|bet| bet.amount - bet.withdrawn)
+                .sum();
+            assert_eq!(open, 100);
+        }
+
+        // bob 对冲后撤回全部A方，B方获胜并已结算 / bob hedges, withdraws all of side A, and B wins
+        fn hedge_withdrawn_to_zero(c: &mut ParaGoalBetting, accounts: &DefaultAccounts<DefaultEnvironment>) -> u128 {
+            set_caller(accounts.alice);
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 100);
+            c.stake(id, Team::TeamA);
+            fund(accounts.bob, 100);
+            c.stake(id, Team::TeamB);
+            fund(accounts.charlie, 50);
+            c.stake(id, Team::TeamB);
+            set_caller(accounts.bob);
+            c.withdraw_stake(id, Team::TeamA, 100);
+            let stakes = c.get_user_stake(id, accounts.bob);
+            assert_eq!((stakes.len(), stakes[0].team), (1, Team::TeamB));
+            // 再次投注不会重复登记参与者 / Staking again does not register the participant twice
+            fund(accounts.bob, 10);
+            c.stake(id, Team::TeamA);
+            set_caller(accounts.bob);
+            c.withdraw_stake(id, Team::TeamA, 10);
+            assert_eq!(c.staker_count(id), 2);
+
+            set_caller(accounts.alice);
+            c.close_match(id);
+            c.settle_match(id, MatchResult::TeamB);
+            id
+        }
+
+        // 中文: 对冲后把一方全部撤回，该方向记录被移除；另一方获胜后仍可领取。
+        // English: Fully withdrawing one side of a hedge removes that position; the winning side still claims.
+        #[ink::test]
+        fn hedge_withdrawn_to_zero_still_claims() {
+            let (mut c, accounts) = setup();
+            let id = hedge_withdrawn_to_zero(&mut c, &accounts);
+            set_caller(accounts.bob);
+            c.claim_payout(id);
+            assert!(c.get_user_stake(id, accounts.bob)[0].claimed);
+        }
+
+        // A方无人投注时 distribute 不会整体回滚 / distribute no longer reverts with nobody left on side A
+        #[ink::test]
+        fn distribute_pays_after_full_withdrawal() {
+            let (mut c, accounts) = setup();
+            let id = hedge_withdrawn_to_zero(&mut c, &accounts);
+            assert_eq!(c.distribute(id, vec![accounts.charlie, accounts.bob]), 2);
+            assert!(c.get_user_stake(id, accounts.bob)[0].claimed);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Match not open")]
+        fn withdraw_stake_rejected_after_close() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 200);
+            c.stake(id, Team::TeamB);
+            set_caller(accounts.alice);
+            c.close_match(id);
+            set_caller(accounts.bob);
+            c.withdraw_stake(id, Team::TeamB, 100);
+        }
+
+        // 中文: 用户持仓一次返回所有投注过的比赛，含状态与可领取金额。
+        // English: get_user_positions returns every match a user staked in, with status and pending payout.
+        #[ink::test]
+        fn user_positions_cover_all_matches() {
+            let (mut c, accounts) = setup();
+            let settled = settled_match(&mut c, &accounts);
+            set_caller(accounts.alice);
+            let open = c.create_match(3, 4, MatchMetadata::default());
+            c.open_match(open);
+            fund(accounts.bob, 50);
+            c.stake(open, Team::TeamB);
+
+            let positions = c.get_user_positions(accounts.bob, 0, 10);
+            assert_eq!(positions.len(), 2);
+            assert_eq!(positions[0].match_id, settled);
+            assert_eq!(positions[0].status, MatchStatus::Settled);
+            assert_eq!(positions[0].pending_payout, 760);
+            assert_eq!(positions[1].match_id, open);
+            assert_eq!(positions[1].stakes[0].amount, 50);
+            assert_eq!(positions[1].pending_payout, 0);
+            assert!(c.get_user_positions(accounts.eve, 0, 10).is_empty());
+        }
+
+        // 中文: 参与者索引按投注顺序记录，每个地址只记录一次，并支持分页。
+        // English: The participant index records each account once in staking order and pages correctly.
+        #[ink::test]
+        fn list_stakers_pages_participants() {
+            let (mut c, accounts) = setup();
+            let id = c.create_match(1, 2, MatchMetadata::default());
+            c.open_match(id);
+            fund(accounts.bob, 10);
+            c.stake(id, Team::TeamA);
+            fund(accounts.charlie, 20);
+            c.stake(id, Team::TeamB);
+            fund(accounts.bob, 5);
+            c.stake(id, Team::TeamA);
+
+            assert_eq!(c.staker_count(id), 2);
+            let all = c.list_stakers(id, 0, 10);
+            assert_eq!(all.len(), 2);
+            assert_eq!(all[0].0, accounts.bob);
+            assert_eq!(all[0].1.amount, 15);
+            assert_eq!(all[1].0, accounts.charlie);
+            let page = c.list_stakers(id, 1, 1);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].0, accounts.charlie);
+            assert!(c.list_stakers(id, 5, 10).is_empty());
+        }
+
+        // 中文: 批量领取合并为一笔转账，未结算/重复/不存在的比赛被跳过。
+        // English: claim_many pays once in aggregate and skips unsettled, repeated or missing matches.
+        #[ink::test]
+        fn claim_many_aggregates_and_skips() {
+            let (mut c, accounts) = setup();
+            let settled = settled_match(&mut c, &accounts);
+            set_caller(accounts.alice);
+            let open = c.create_match(3, 4, MatchMetadata::default());
+            c.open_match(open);
+            fund(accounts.bob, 50);
+            c.stake(open, Team::TeamB);
+
+            let bob_before = balance_of(accounts.bob);
+            set_caller(accounts.bob);
+            let outcomes = c.claim_many(vec![settled, open, settled, 999]);
+            assert_eq!(
+                outcomes,
+                vec![
+                    (settled, ClaimOutcome::Paid(760)),
+                    (open, ClaimOutcome::NotSettled),
+                    (settled, ClaimOutcome::AlreadyClaimed),
+                    (999, ClaimOutcome::MatchNotFound),
+                ]
+            );
+            assert_eq!(balance_of(accounts.bob), bob_before + 760);
+        }
+
+        // 中文: 奖金无法计算的比赛被标记为 Unpayable 并跳过，其余比赛照常领取。
+        // English: A match whose payout cannot be computed is reported as Unpayable and skipped; the rest still pay.
+        #[ink::test]
+        fn claim_many_skips_unpayable_match() {
+            let (mut c, accounts) = setup();
+            let settled = settled_match(&mut c, &accounts);
+            let broken = settled_match(&mut c, &accounts);
+            let mut m = c.load_match(broken).unwrap();
+            m.pool_amount = Balance::MAX; // 让奖池份额溢出 / Force the pool share to overflow
+            c.save_match(&m);
+
+            set_caller(accounts.bob);
+            let outcomes = c.claim_many(vec![broken, settled]);
+            assert_eq!(outcomes, vec![(broken, ClaimOutcome::Unpayable), (settled, ClaimOutcome::Paid(760))]);
+            assert!(!c.get_user_stake(broken, accounts.bob)[0].claimed);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Batch too large")]
+        fn distribute_rejects_oversized_batch() {
+            let (mut c, accounts) = setup();
+            let id = settled_match(&mut c, &accounts);
+            c.distribute(id, vec![accounts.bob; MAX_DISTRIBUTE_BATCH + 1]);
         }
     }
 }